@@ -13,7 +13,11 @@ pub fn main() -> CliResult {
 
     let config_value = args.get_one::<String>("config").map(PathBuf::from);
 
-    let config_manager = config::ConfigManager::with_location(config_value).read()?;
+    let diary_name = args.get_one::<String>("diary").cloned();
+
+    let config_manager = config::ConfigManager::with_location(config_value)
+        .read()?
+        .with_diary(diary_name);
 
     let (cmd, subcommand_args) = match args.subcommand() {
         Some((cmd, args)) => (cmd, args),
@@ -37,6 +41,14 @@ fn cli() -> Command {
                 .help("Sets a custom config file")
                 .num_args(1),
         )
+        .arg(
+            Arg::new("diary")
+                .long("diary")
+                .value_name("NAME")
+                .help("Use one of the diaries in the config file, by name")
+                .num_args(1)
+                .global(true),
+        )
         .subcommands(commands::builtin())
 }
 
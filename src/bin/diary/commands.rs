@@ -8,14 +8,36 @@ pub fn builtin() -> Vec<Command> {
         add::cli(),
         open::cli(),
         commit::cli(),
+        convert::cli(),
+        migrate::cli(),
+        list::cli(),
+        search::cli(),
+        show::cli(),
+        stats::cli(),
+        cal::cli(),
+        tags::cli(),
+        tag::cli(),
+        export::cli(),
+        import::cli(),
     ]
 }
 
 pub mod add;
+pub mod cal;
 pub mod commit;
+pub mod convert;
+pub mod export;
+pub mod import;
 pub mod init;
+pub mod list;
+pub mod migrate;
 pub mod new;
 pub mod open;
+pub mod search;
+pub mod show;
+pub mod stats;
+pub mod tag;
+pub mod tags;
 
 pub fn builtin_exec(cmd: &str) -> Option<fn(ConfigManager, &ArgMatches) -> diary::CliResult> {
     let f = match cmd {
@@ -24,6 +46,17 @@ pub fn builtin_exec(cmd: &str) -> Option<fn(ConfigManager, &ArgMatches) -> diary
         "add" => add::exec,
         "open" => open::exec,
         "commit" => commit::exec,
+        "convert" => convert::exec,
+        "migrate" => migrate::exec,
+        "list" => list::exec,
+        "search" => search::exec,
+        "show" => show::exec,
+        "stats" => stats::exec,
+        "cal" => cal::exec,
+        "tags" => tags::exec,
+        "tag" => tag::exec,
+        "export" => export::exec,
+        "import" => import::exec,
         _ => return None,
     };
     Some(f)
@@ -16,15 +16,35 @@ pub fn cli() -> Command {
                 .value_name("TAG")
                 .help("Add a tag above the entry text."),
         )
+        .arg(
+            Arg::new("time")
+                .long("time")
+                .num_args(0)
+                .conflicts_with("no-time")
+                .help("Add the current time above the entry text."),
+        )
+        .arg(
+            Arg::new("no-time")
+                .long("no-time")
+                .num_args(0)
+                .help("Do not add the current time, even if timestamp_adds is set in the config."),
+        )
         .arg(
             Arg::new("content")
                 .num_args(0..)
                 .value_name("CONTENT")
                 .help("entry text"),
         )
+        .arg(
+            Arg::new("name")
+                .long("name")
+                .short('n')
+                .value_name("NAME")
+                .help("Add to a named entry instead of the main one."),
+        )
 }
 
-fn args_to_add_opts(args: &ArgMatches) -> AddOptions {
+fn args_to_add_opts(args: &ArgMatches, timestamp_adds: bool) -> AddOptions {
     let tag = args.get_one::<String>("tag").cloned();
     let content = args.get_many::<String>("content").map(|values_ref| {
         values_ref
@@ -32,14 +52,42 @@ fn args_to_add_opts(args: &ArgMatches) -> AddOptions {
             .collect::<Vec<String>>()
             .join(" ")
     });
-    AddOptions { tag, content }
+    let timestamp = args.get_flag("time") || (timestamp_adds && !args.get_flag("no-time"));
+    let time = timestamp.then(|| Local::now().time());
+    let name = args.get_one::<String>("name").cloned();
+    AddOptions {
+        tag,
+        content,
+        time,
+        name,
+    }
 }
 
 pub fn exec(config_manager: ConfigManager, args: &ArgMatches) -> CliResult {
-    let opts = args_to_add_opts(args);
+    let opts = args_to_add_opts(args, config_manager.config().selected()?.timestamp_adds());
     let date = Local::now();
     let diary = Diary::from_config(config_manager.config())?;
     add(&opts, &diary, &date, edit::edit)?;
     println!("Added content."); //uncovered.
     Ok(()) //uncovered.
 }
+
+#[cfg(test)]
+mod test {
+    use super::{args_to_add_opts, cli};
+
+    #[test]
+    fn test_time_flags() {
+        let matches = cli().try_get_matches_from(vec!["add", "--time"]).unwrap();
+        assert!(args_to_add_opts(&matches, false).time.is_some());
+
+        let matches = cli().try_get_matches_from(vec!["add"]).unwrap();
+        assert!(args_to_add_opts(&matches, true).time.is_some());
+        assert!(args_to_add_opts(&matches, false).time.is_none());
+
+        let matches = cli()
+            .try_get_matches_from(vec!["add", "--no-time"])
+            .unwrap();
+        assert!(args_to_add_opts(&matches, true).time.is_none());
+    }
+}
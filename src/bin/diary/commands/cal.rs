@@ -0,0 +1,60 @@
+use std::{
+    env,
+    io::{stdout, IsTerminal},
+};
+
+use chrono::{Datelike, Local};
+use clap::{Arg, ArgMatches, Command};
+use diary::{
+    config::ConfigManager,
+    ops::cal::{cal, CalOptions},
+    CliResult, Diary,
+};
+
+pub fn cli() -> Command {
+    Command::new("cal")
+        .about("Draw a calendar with the days that have entries highlighted by word count.")
+        .arg(
+            Arg::new("year")
+                .long("year")
+                .value_name("YEAR")
+                .value_parser(clap::value_parser!(i32))
+                .help("The year to draw. Defaults to the current year."),
+        )
+        .arg(
+            Arg::new("month")
+                .long("month")
+                .value_name("MONTH")
+                .value_parser(clap::value_parser!(u32).range(1..=12))
+                .help("Draw a single month, 1 to 12, rather than the whole year."),
+        )
+        .arg(
+            Arg::new("no-colour")
+                .long("no-colour")
+                .visible_alias("no-color")
+                .num_args(0)
+                .help("Mark days with symbols rather than colours."),
+        )
+}
+
+fn args_to_cal_opts(args: &ArgMatches) -> CalOptions {
+    let year = args
+        .get_one::<i32>("year")
+        .copied()
+        .unwrap_or_else(|| Local::now().year());
+    let month = args.get_one::<u32>("month").copied();
+    let colour =
+        !args.get_flag("no-colour") && env::var_os("NO_COLOR").is_none() && stdout().is_terminal();
+    CalOptions {
+        year,
+        month,
+        colour,
+    }
+}
+
+pub fn exec(config_manager: ConfigManager, args: &ArgMatches) -> CliResult {
+    let opts = args_to_cal_opts(args);
+    let diary = Diary::from_config(config_manager.config())?;
+    print!("{}", cal(&opts, &diary)?);
+    Ok(())
+}
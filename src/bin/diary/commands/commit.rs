@@ -32,6 +32,13 @@ pub fn cli() -> Command {
                 .num_args(0)
                 .help("Whether or not to immediately push the commit."),
         )
+        .arg(
+            Arg::new("name")
+                .long("name")
+                .short('n')
+                .value_name("NAME")
+                .help("Commit a named entry instead of the main one."),
+        )
 }
 
 fn args_to_commit_opts(args: &ArgMatches) -> Result<CommitOptions, ParseError> {
@@ -41,10 +48,12 @@ fn args_to_commit_opts(args: &ArgMatches) -> Result<CommitOptions, ParseError> {
         .cloned()
         .unwrap_or_else(|| "Added an entry.".to_owned());
     let push = args.get_flag("push");
+    let name = args.get_one::<String>("name").cloned();
     Ok(CommitOptions {
         entry_date,
         message,
         push,
+        name,
     })
 }
 
@@ -0,0 +1,60 @@
+use clap::{Arg, ArgMatches, Command};
+use diary::{
+    config::{ConfigBuilder, ConfigManager},
+    ops::convert::{convert, ConvertOptions},
+    CliResult, Diary,
+};
+
+pub fn cli() -> Command {
+    Command::new("convert")
+        .about("Convert every entry to another file type.")
+        .arg(
+            Arg::new("to")
+                .long("to")
+                .required(true)
+                .value_name("FILETYPE")
+                .help("The file type to convert to. One of md, rst, org, adoc or txt."),
+        )
+        .arg(
+            Arg::new("dry-run")
+                .long("dry-run")
+                .num_args(0)
+                .help("Show the entries that would be converted without changing anything."),
+        )
+}
+
+fn args_to_convert_opts(args: &ArgMatches) -> ConvertOptions {
+    let to = args
+        .get_one::<String>("to")
+        .cloned()
+        .expect("The to argument is required.");
+    let dry_run = args.get_flag("dry-run");
+    ConvertOptions { to, dry_run }
+}
+
+pub fn exec(config_manager: ConfigManager, args: &ArgMatches) -> CliResult {
+    let opts = args_to_convert_opts(args);
+    let diary = Diary::from_config(config_manager.config())?;
+    let conversions = convert(&opts, &diary)?;
+
+    for conversion in &conversions {
+        println!(
+            "{} -> {}",
+            conversion.from.display(),
+            conversion.to.display()
+        );
+    }
+    if opts.dry_run {
+        println!("Dry run, {} entries would be converted.", conversions.len());
+        return Ok(());
+    }
+
+    let new_cfg = config_manager
+        .config()
+        .clone()
+        .update_selected(|cfg| ConfigBuilder::from(cfg).file_type(opts.to).build())?;
+    config_manager.update_config(new_cfg).write()?;
+
+    println!("Converted {} entries.", conversions.len());
+    Ok(())
+}
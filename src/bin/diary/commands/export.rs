@@ -0,0 +1,159 @@
+use std::{fs, path::PathBuf};
+
+use clap::{Arg, ArgMatches, Command};
+use diary::{
+    config::ConfigManager,
+    errors::DiaryError,
+    ops::export::{
+        book::{book, BookOptions},
+        epub::{export_epub, EpubOptions},
+        html::{export_html, HtmlOptions},
+        records::{records, to_csv, to_json, RecordOptions},
+    },
+    utils::date::{date_range_args, parse_date_range},
+    CliResult, Diary,
+};
+
+fn range_cli(name: &'static str, about: &'static str) -> Command {
+    Command::new(name).about(about).args(date_range_args()).arg(
+        Arg::new("output")
+            .short('o')
+            .long("output")
+            .value_name("FILE")
+            .value_parser(clap::value_parser!(PathBuf))
+            .help("The file to write to. Defaults to printing the export."),
+    )
+}
+
+pub fn cli() -> Command {
+    Command::new("export")
+        .about("Export the diary to another format.")
+        .subcommand_required(true)
+        .arg_required_else_help(true)
+        .subcommand(
+            Command::new("html")
+                .about("Export the diary as a static HTML site.")
+                .arg(
+                    Arg::new("out_dir")
+                        .required(true)
+                        .value_name("OUT_DIR")
+                        .value_parser(clap::value_parser!(PathBuf))
+                        .help("The folder to write the site to, created if needed."),
+                ),
+        )
+        .subcommand(range_cli(
+            "json",
+            "Export one JSON record per entry, with its sections and word counts.",
+        ))
+        .subcommand(range_cli(
+            "csv",
+            "Export one CSV row per entry, with its sections as a JSON array.",
+        ))
+        .subcommand(range_cli(
+            "book",
+            "Export the entries as one document with a table of contents.",
+        ))
+        .subcommand(
+            Command::new("epub")
+                .about("Export the entries as an EPUB book, with a chapter for each month.")
+                .args(date_range_args())
+                .arg(
+                    Arg::new("out_file")
+                        .required(true)
+                        .value_name("OUT_FILE")
+                        .value_parser(clap::value_parser!(PathBuf))
+                        .help("The file to write the book to, e.g. diary.epub."),
+                ),
+        )
+}
+
+fn args_to_html_opts(args: &ArgMatches) -> HtmlOptions {
+    let out_dir = args
+        .get_one::<PathBuf>("out_dir")
+        .cloned()
+        .expect("The out_dir argument is required.");
+    HtmlOptions { out_dir }
+}
+
+fn exec_html(diary: &Diary, args: &ArgMatches) -> CliResult {
+    let opts = args_to_html_opts(args);
+    let export = export_html(&opts, diary)?;
+    println!(
+        "Exported {} entries and {} attachments to {}.",
+        export.entries,
+        export.attachments,
+        opts.out_dir.display()
+    );
+    Ok(())
+}
+
+/// Writes an export to the file given by `--output`, or prints it.
+fn write_export(args: &ArgMatches, export: &str) -> CliResult {
+    match args.get_one::<PathBuf>("output") {
+        Some(path) => {
+            fs::write(path, export)?;
+            println!("Exported to {}.", path.display());
+        }
+        None => print!("{}", export),
+    }
+    Ok(())
+}
+
+fn args_to_record_opts(args: &ArgMatches) -> Result<RecordOptions, DiaryError> {
+    let range = parse_date_range(args)?;
+    Ok(RecordOptions { range })
+}
+
+fn exec_records(diary: &Diary, args: &ArgMatches, csv: bool) -> CliResult {
+    let opts = args_to_record_opts(args)?;
+    let records = records(&opts, diary)?;
+    let export = if csv {
+        to_csv(&records)?
+    } else {
+        to_json(&records)?
+    };
+    write_export(args, &export)
+}
+
+fn args_to_book_opts(args: &ArgMatches) -> Result<BookOptions, DiaryError> {
+    let range = parse_date_range(args)?;
+    Ok(BookOptions { range })
+}
+
+fn exec_book(diary: &Diary, args: &ArgMatches) -> CliResult {
+    let opts = args_to_book_opts(args)?;
+    write_export(args, &book(&opts, diary)?)
+}
+
+fn args_to_epub_opts(args: &ArgMatches) -> Result<EpubOptions, DiaryError> {
+    let range = parse_date_range(args)?;
+    let output = args
+        .get_one::<PathBuf>("out_file")
+        .cloned()
+        .expect("The out_file argument is required.");
+    Ok(EpubOptions { range, output })
+}
+
+fn exec_epub(diary: &Diary, args: &ArgMatches) -> CliResult {
+    let opts = args_to_epub_opts(args)?;
+    let export = export_epub(&opts, diary)?;
+    println!(
+        "Exported {} entries in {} chapters to {}.",
+        export.entries,
+        export.chapters,
+        opts.output.display()
+    );
+    Ok(())
+}
+
+pub fn exec(config_manager: ConfigManager, args: &ArgMatches) -> CliResult {
+    let diary = Diary::from_config(config_manager.config())?;
+    match args.subcommand() {
+        Some(("html", sub_args)) => exec_html(&diary, sub_args),
+        Some(("json", sub_args)) => exec_records(&diary, sub_args, false),
+        Some(("csv", sub_args)) => exec_records(&diary, sub_args, true),
+        Some(("book", sub_args)) => exec_book(&diary, sub_args),
+        Some(("epub", sub_args)) => exec_epub(&diary, sub_args),
+        _ => unreachable!("A subcommand is required."),
+    }
+}
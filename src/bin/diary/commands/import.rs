@@ -0,0 +1,73 @@
+use std::{fs, path::PathBuf};
+
+use clap::{Arg, ArgMatches, Command};
+use diary::{
+    config::ConfigManager,
+    ops::import::{dayone, import, jrnl, ImportOptions},
+    CliResult, Diary,
+};
+
+fn source_cli(name: &'static str, about: &'static str, file_help: &'static str) -> Command {
+    Command::new(name)
+        .about(about)
+        .arg(
+            Arg::new("file")
+                .required(true)
+                .value_name("FILE")
+                .value_parser(clap::value_parser!(PathBuf))
+                .help(file_help),
+        )
+        .arg(
+            Arg::new("merge")
+                .long("merge")
+                .num_args(0)
+                .help("Add to entries that already exist rather than refusing to import."),
+        )
+}
+
+pub fn cli() -> Command {
+    Command::new("import")
+        .about("Import entries from another journaling tool.")
+        .subcommand_required(true)
+        .arg_required_else_help(true)
+        .subcommand(source_cli(
+            "jrnl",
+            "Import the entries of a jrnl plain text file.",
+            "The jrnl file, e.g. from `jrnl --export txt`.",
+        ))
+        .subcommand(source_cli(
+            "dayone",
+            "Import the entries of a Day One JSON export.",
+            "The JSON file of the Day One export.",
+        ))
+}
+
+fn args_to_import_opts(args: &ArgMatches) -> ImportOptions {
+    let merge = args.get_flag("merge");
+    ImportOptions { merge }
+}
+
+pub fn exec(config_manager: ConfigManager, args: &ArgMatches) -> CliResult {
+    let diary = Diary::from_config(config_manager.config())?;
+    let (source, sub_args) = args.subcommand().expect("A subcommand is required.");
+    let opts = args_to_import_opts(sub_args);
+    let path = sub_args
+        .get_one::<PathBuf>("file")
+        .expect("The file argument is required.");
+    let content = fs::read_to_string(path)?;
+
+    let entries = match source {
+        "jrnl" => jrnl::parse(&content)?,
+        "dayone" => dayone::parse(&content)?,
+        _ => unreachable!("Only the jrnl and dayone subcommands exist."),
+    };
+    let summary = import(&opts, &diary, entries, edit::edit)?;
+    println!(
+        "Imported {} entries, creating {} new diary entries.",
+        summary.imported, summary.created
+    );
+    if summary.skipped > 0 {
+        println!("Skipped {} entries already in the diary.", summary.skipped);
+    }
+    Ok(())
+}
@@ -3,14 +3,14 @@ use std::{fs::canonicalize, path::PathBuf};
 
 use clap::{error::ErrorKind, Arg, ArgMatches, Command, Error};
 use diary::{
-    config::{Config, ConfigManager},
+    config::{Config, ConfigBuilder, ConfigManager},
     ops::{init, InitOptions},
     process_file_type, CliResult,
 };
 
 pub fn cli() -> Command {
     Command::new("init")
-        .about("Create a new diary folder and config file.")
+        .about("Create a new diary folder and config file. Use --diary to add a named diary to the config.")
         .arg(
             Arg::new("path")
                 .default_value(".")
@@ -31,10 +31,9 @@ pub fn cli() -> Command {
                 .help("Sets the diary files name prefix."),
         )
         .arg(
-            Arg::new("filetype")
-                .long("filetype")
-                .num_args(1)
-                .help("Sets the file type to use for diary entries."),
+            Arg::new("filetype").long("filetype").num_args(1).help(
+                "Sets the file type to use for diary entries. One of md, rst, org, adoc or txt.",
+            ),
         )
 }
 
@@ -60,11 +59,12 @@ fn args_to_init_ops(args: &ArgMatches) -> Result<init::InitOptions, Error> {
 }
 
 fn build_new_config(
+    base: ConfigBuilder,
     diary_path: PathBuf,
     prefix: Option<String>,
     processed_file_type: Option<impl AsRef<str>>,
 ) -> Config {
-    let new_cfg_builder = Config::builder().diary_path(
+    let new_cfg_builder = base.diary_path(
         canonicalize(diary_path).expect("Attempted to canonicalize a path that does not exist."),
     );
 
@@ -85,9 +85,25 @@ pub fn exec(config_manager: ConfigManager, args: &ArgMatches) -> CliResult {
         process_file_type(args.get_one::<String>("filetype").map(|x| x.as_str()))?;
 
     let opts = args_to_init_ops(args)?;
-    let path = init::init(&opts, config_manager.config().diary_path())?;
+    let config = config_manager.config().clone();
 
-    let new_cfg = build_new_config(path, opts.prefix, processed_file_type);
+    let new_cfg = match args.get_one::<String>("diary") {
+        Some(name) => {
+            let potential_path = config
+                .diaries()
+                .get(name)
+                .map_or_else(PathBuf::new, |diary| diary.diary_path().clone());
+            let path = init::init(&opts, &potential_path)?;
+            let diary_cfg =
+                build_new_config(Config::builder(), path, opts.prefix, processed_file_type);
+            config.with_diary(name, diary_cfg)
+        }
+        None => {
+            let path = init::init(&opts, config.diary_path())?;
+            let base = ConfigBuilder::from(config);
+            build_new_config(base, path, opts.prefix, processed_file_type)
+        }
+    };
 
     config_manager.update_config(new_cfg).write()?;
 
@@ -0,0 +1,53 @@
+use clap::{Arg, ArgMatches, Command};
+use diary::{
+    config::ConfigManager,
+    errors::DiaryError,
+    ops::list::{list, ListOptions},
+    utils::date::{date_range_args, parse_date_range},
+    CliResult, Diary,
+};
+
+pub fn cli() -> Command {
+    Command::new("list")
+        .about("List the entries of the diary, optionally within a range of dates.")
+        .args(date_range_args())
+        .arg(
+            Arg::new("json")
+                .long("json")
+                .num_args(0)
+                .help("Print the entries as JSON."),
+        )
+}
+
+fn args_to_list_opts(args: &ArgMatches) -> Result<ListOptions, DiaryError> {
+    let range = parse_date_range(args)?;
+    Ok(ListOptions { range })
+}
+
+pub fn exec(config_manager: ConfigManager, args: &ArgMatches) -> CliResult {
+    let opts = args_to_list_opts(args)?;
+    let diary = Diary::from_config(config_manager.config())?;
+    let summaries = list(&opts, &diary)?;
+
+    if args.get_flag("json") {
+        let json = serde_json::to_string_pretty(&summaries)?;
+        println!("{}", json);
+        return Ok(());
+    }
+
+    for summary in &summaries {
+        let path = summary
+            .path
+            .strip_prefix(diary.diary_path())
+            .unwrap_or(&summary.path);
+        let line = format!(
+            "{}  {}  {} bytes  {}",
+            summary.date,
+            path.display(),
+            summary.size,
+            summary.tags.join(", ")
+        );
+        println!("{}", line.trim_end());
+    }
+    Ok(())
+}
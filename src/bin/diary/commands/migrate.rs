@@ -0,0 +1,67 @@
+use clap::{Arg, ArgGroup, ArgMatches, Command};
+use diary::{
+    config::ConfigManager,
+    errors::DiaryError,
+    ops::migrate::{migrate, MigrateOptions},
+    utils::layout::Layout,
+    CliResult, Diary,
+};
+
+pub fn cli() -> Command {
+    Command::new("migrate")
+        .about("Move entries written under an old prefix or layout to where the diary now expects them.")
+        .arg(
+            Arg::new("from-prefix")
+                .long("from-prefix")
+                .value_name("PREFIX")
+                .help("The prefix the entries were written with, if it has changed."),
+        )
+        .arg(
+            Arg::new("from-layout")
+                .long("from-layout")
+                .value_name("LAYOUT")
+                .help("The layout the entries were written with, if it has changed."),
+        )
+        .group(
+            ArgGroup::new("from")
+                .args(["from-prefix", "from-layout"])
+                .multiple(true)
+                .required(true),
+        )
+        .arg(
+            Arg::new("dry-run")
+                .long("dry-run")
+                .num_args(0)
+                .help("Show the entries that would be moved without changing anything."),
+        )
+}
+
+fn args_to_migrate_opts(args: &ArgMatches) -> Result<MigrateOptions, DiaryError> {
+    let from_prefix = args.get_one::<String>("from-prefix").cloned();
+    let from_layout = args
+        .get_one::<String>("from-layout")
+        .map(|layout| Layout::parse(layout))
+        .transpose()?;
+    let dry_run = args.get_flag("dry-run");
+    Ok(MigrateOptions {
+        from_prefix,
+        from_layout,
+        dry_run,
+    })
+}
+
+pub fn exec(config_manager: ConfigManager, args: &ArgMatches) -> CliResult {
+    let opts = args_to_migrate_opts(args)?;
+    let diary = Diary::from_config(config_manager.config())?;
+    let migrations = migrate(&opts, &diary)?;
+
+    for migration in &migrations {
+        println!("{} -> {}", migration.from.display(), migration.to.display());
+    }
+    if opts.dry_run {
+        println!("Dry run, {} entries would be moved.", migrations.len());
+    } else {
+        println!("Moved {} entries.", migrations.len());
+    }
+    Ok(())
+}
@@ -17,11 +17,31 @@ pub fn cli() -> Command {
                 .num_args(0)
                 .help("Open the new entry after creation."),
         )
+        .arg(
+            Arg::new("template")
+                .long("template")
+                .short('t')
+                .value_name("NAME")
+                .help("Use a specific template from the templates folder for the entry."),
+        )
+        .arg(
+            Arg::new("name")
+                .long("name")
+                .short('n')
+                .value_name("NAME")
+                .help("Create a named entry alongside today's main entry, e.g. evening."),
+        )
 }
 
 fn args_to_new_opts(args: &ArgMatches) -> NewOptions {
     let open = args.get_flag("open");
-    NewOptions { open }
+    let template = args.get_one::<String>("template").cloned();
+    let name = args.get_one::<String>("name").cloned();
+    NewOptions {
+        open,
+        template,
+        name,
+    }
 }
 
 pub fn exec(config_manager: ConfigManager, args: &ArgMatches) -> CliResult {
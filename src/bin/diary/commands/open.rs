@@ -17,11 +17,19 @@ pub fn cli() -> Command {
                 .value_name("date")
                 .help("Open a specific diary entry. Use the %Y-%m-%d format."),
         )
+        .arg(
+            Arg::new("name")
+                .long("name")
+                .short('n')
+                .value_name("NAME")
+                .help("Open a named entry instead of the main one."),
+        )
 }
 
 fn args_to_open_opts(args: &ArgMatches) -> Result<OpenFileOptions, ParseError> {
     let entry_date = parse_date_option(args)?;
-    Ok(OpenFileOptions { entry_date })
+    let name = args.get_one::<String>("name").cloned();
+    Ok(OpenFileOptions { entry_date, name })
 }
 
 pub fn exec(config_manager: ConfigManager, args: &ArgMatches) -> CliResult {
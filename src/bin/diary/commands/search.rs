@@ -0,0 +1,105 @@
+use clap::{Arg, ArgMatches, Command};
+use diary::{
+    config::ConfigManager,
+    ops::search::{search, SearchOptions},
+    CliResult, Diary,
+};
+
+pub fn cli() -> Command {
+    Command::new("search")
+        .about("Search every entry for a pattern.")
+        .arg(
+            Arg::new("pattern")
+                .required(true)
+                .help("The text to search for."),
+        )
+        .arg(
+            Arg::new("regex")
+                .long("regex")
+                .short('e')
+                .num_args(0)
+                .help("Treat the pattern as a regular expression."),
+        )
+        .arg(
+            Arg::new("ignore-case")
+                .long("ignore-case")
+                .short('i')
+                .num_args(0)
+                .help("Ignore case when matching."),
+        )
+        .arg(
+            Arg::new("tag")
+                .long("tag")
+                .short('t')
+                .value_name("TAG")
+                .help("Only search sections with this tag."),
+        )
+        .arg(
+            Arg::new("context")
+                .long("context")
+                .short('C')
+                .value_name("LINES")
+                .value_parser(clap::value_parser!(usize))
+                .default_value("1")
+                .help("The number of lines to show around each match."),
+        )
+}
+
+fn args_to_search_opts(args: &ArgMatches) -> SearchOptions {
+    let pattern = args
+        .get_one::<String>("pattern")
+        .expect("The pattern is required.")
+        .clone();
+    let regex = args.get_flag("regex");
+    let ignore_case = args.get_flag("ignore-case");
+    let tag = args.get_one::<String>("tag").cloned();
+    let context = *args
+        .get_one::<usize>("context")
+        .expect("The context has a default.");
+    SearchOptions {
+        pattern,
+        regex,
+        ignore_case,
+        tag,
+        context,
+    }
+}
+
+pub fn exec(config_manager: ConfigManager, args: &ArgMatches) -> CliResult {
+    let opts = args_to_search_opts(args);
+    let diary = Diary::from_config(config_manager.config())?;
+    let matches = search(&opts, &diary)?;
+
+    for (i, search_match) in matches.iter().enumerate() {
+        if i > 0 && opts.context > 0 {
+            println!("--");
+        }
+        let path = search_match
+            .path
+            .strip_prefix(diary.diary_path())
+            .unwrap_or(&search_match.path);
+        let section = search_match
+            .section
+            .as_ref()
+            .map(|section| format!(" [{}]", section))
+            .unwrap_or_default();
+        println!(
+            "{}{} {}:{}",
+            search_match.date,
+            section,
+            path.display(),
+            search_match.line_number
+        );
+        for line in &search_match.before {
+            println!("  {}", line);
+        }
+        println!("> {}", search_match.line);
+        for line in &search_match.after {
+            println!("  {}", line);
+        }
+    }
+    if matches.is_empty() {
+        println!("No matches found.");
+    }
+    Ok(())
+}
@@ -0,0 +1,107 @@
+use std::{
+    env,
+    io::Write,
+    process::{Command as Process, Stdio},
+    str::FromStr,
+};
+
+use chrono::{Local, NaiveDate};
+use clap::{Arg, ArgMatches, Command};
+use diary::{
+    config::ConfigManager,
+    ops::show::{show, EntrySelection, ShowOptions},
+    utils::date::{date_range_args, parse_date_range, DateRange},
+    CliResult, Diary,
+};
+
+pub fn cli() -> Command {
+    Command::new("show")
+        .about("Print diary entries. Defaults to today's.")
+        .arg(
+            Arg::new("date")
+                .long("date")
+                .short('d')
+                .value_name("date")
+                .conflicts_with_all(["from", "to", "month", "year", "last"])
+                .help("Print a specific diary entry. Use the %Y-%m-%d format."),
+        )
+        .args(date_range_args())
+        .arg(
+            Arg::new("last")
+                .long("last")
+                .value_name("N")
+                .value_parser(clap::value_parser!(usize))
+                .conflicts_with_all(["from", "to", "month", "year"])
+                .help("Print the last N entries."),
+        )
+        .arg(
+            Arg::new("raw")
+                .long("raw")
+                .num_args(0)
+                .help("Print the entry files as they are rather than as plain text."),
+        )
+        .arg(
+            Arg::new("pager")
+                .long("pager")
+                .num_args(0)
+                .help("Page the output with $PAGER, or less if it is not set."),
+        )
+}
+
+fn args_to_show_opts(args: &ArgMatches) -> anyhow::Result<ShowOptions> {
+    let selection = match args.get_one::<usize>("last") {
+        Some(count) => EntrySelection::Last(*count),
+        None => {
+            let range = parse_date_range(args)?;
+            if range == DateRange::default() {
+                let date = match args.get_one::<String>("date") {
+                    Some(value) => NaiveDate::from_str(value)?,
+                    None => Local::now().date_naive(),
+                };
+                EntrySelection::Range(DateRange::day(date))
+            } else {
+                EntrySelection::Range(range)
+            }
+        }
+    };
+    let raw = args.get_flag("raw");
+    Ok(ShowOptions { selection, raw })
+}
+
+/// Writes the content to the user's pager, falling back to stdout if it can't be started.
+fn page(content: &str) -> std::io::Result<()> {
+    let pager = env::var("PAGER").unwrap_or_else(|_| "less".to_owned());
+    let mut words = pager.split_whitespace();
+    let Some(program) = words.next() else {
+        print!("{}", content);
+        return Ok(());
+    };
+
+    match Process::new(program)
+        .args(words)
+        .stdin(Stdio::piped())
+        .spawn()
+    {
+        Ok(mut child) => {
+            if let Some(mut stdin) = child.stdin.take() {
+                stdin.write_all(content.as_bytes())?;
+            }
+            child.wait()?;
+        }
+        Err(_) => print!("{}", content),
+    }
+    Ok(())
+}
+
+pub fn exec(config_manager: ConfigManager, args: &ArgMatches) -> CliResult {
+    let opts = args_to_show_opts(args)?;
+    let diary = Diary::from_config(config_manager.config())?;
+    let content = show(&opts, &diary)?;
+
+    if args.get_flag("pager") {
+        page(&content)?;
+    } else {
+        print!("{}", content);
+    }
+    Ok(())
+}
@@ -0,0 +1,94 @@
+use chrono::Local;
+use clap::{Arg, ArgMatches, Command};
+use diary::{
+    config::ConfigManager,
+    errors::DiaryError,
+    ops::stats::{stats, PeriodStats, StatsOptions},
+    utils::date::{date_range_args, parse_date_range},
+    CliResult, Diary,
+};
+
+pub fn cli() -> Command {
+    Command::new("stats")
+        .about("Show writing statistics: words, streaks, busiest weekdays and top tags.")
+        .args(date_range_args())
+        .arg(
+            Arg::new("top")
+                .long("top")
+                .value_name("N")
+                .value_parser(clap::value_parser!(usize))
+                .default_value("5")
+                .help("The number of most-used tags to show."),
+        )
+        .arg(
+            Arg::new("json")
+                .long("json")
+                .num_args(0)
+                .help("Print the statistics as JSON."),
+        )
+}
+
+fn args_to_stats_opts(args: &ArgMatches) -> Result<StatsOptions, DiaryError> {
+    let range = parse_date_range(args)?;
+    let top_tags = *args
+        .get_one::<usize>("top")
+        .expect("The top has a default.");
+    Ok(StatsOptions { range, top_tags })
+}
+
+fn print_periods(heading: &str, periods: &[PeriodStats]) {
+    println!();
+    println!(
+        "{:<8}  {:>7}  {:>7}  {:>15}",
+        heading, "Entries", "Words", "Words per entry"
+    );
+    for period in periods {
+        println!(
+            "{:<8}  {:>7}  {:>7}  {:>15.1}",
+            period.period, period.entries, period.words, period.words_per_entry
+        );
+    }
+}
+
+pub fn exec(config_manager: ConfigManager, args: &ArgMatches) -> CliResult {
+    let opts = args_to_stats_opts(args)?;
+    let diary = Diary::from_config(config_manager.config())?;
+    let stats = stats(&opts, &diary, Local::now().date_naive())?;
+
+    if args.get_flag("json") {
+        let json = serde_json::to_string_pretty(&stats)?;
+        println!("{}", json);
+        return Ok(());
+    }
+
+    println!("{:<16} {}", "Entries", stats.entries);
+    println!("{:<16} {}", "Words", stats.words);
+    println!("{:<16} {:.1}", "Words per entry", stats.words_per_entry);
+    println!("{:<16} {} days", "Current streak", stats.current_streak);
+    println!("{:<16} {} days", "Longest streak", stats.longest_streak);
+
+    print_periods("Month", &stats.months);
+    print_periods("Year", &stats.years);
+
+    println!();
+    println!("{:<10}  {:>7}", "Weekday", "Entries");
+    for weekday in &stats.weekdays {
+        println!("{:<10}  {:>7}", weekday.weekday, weekday.entries);
+    }
+
+    if !stats.top_tags.is_empty() {
+        let width = stats
+            .top_tags
+            .iter()
+            .map(|tag| tag.tag.chars().count())
+            .chain(Some(3))
+            .max()
+            .unwrap_or_default();
+        println!();
+        println!("{:<width$}  {:>8}", "Tag", "Sections", width = width);
+        for tag in &stats.top_tags {
+            println!("{:<width$}  {:>8}", tag.tag, tag.sections, width = width);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use clap::{Arg, ArgMatches, Command};
+use diary::{
+    config::ConfigManager,
+    errors::DiaryError,
+    ops::tags::{tag, TagOptions},
+    utils::date::{date_range_args, parse_date_range},
+    CliResult, Diary,
+};
+
+pub fn cli() -> Command {
+    Command::new("tag")
+        .about("Print every section written under a tag, oldest first.")
+        .arg(Arg::new("name").required(true).help("The tag to collect."))
+        .args(date_range_args())
+}
+
+fn args_to_tag_opts(args: &ArgMatches) -> Result<TagOptions, DiaryError> {
+    let tag = args
+        .get_one::<String>("name")
+        .expect("The tag is required.")
+        .clone();
+    let range = parse_date_range(args)?;
+    Ok(TagOptions { tag, range })
+}
+
+pub fn exec(config_manager: ConfigManager, args: &ArgMatches) -> CliResult {
+    let opts = args_to_tag_opts(args)?;
+    let diary = Diary::from_config(config_manager.config())?;
+    print!("{}", tag(&opts, &diary)?);
+    Ok(())
+}
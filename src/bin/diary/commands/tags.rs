@@ -0,0 +1,41 @@
+use clap::{Arg, ArgMatches, Command};
+use diary::{config::ConfigManager, ops::tags::tags, CliResult, Diary};
+
+pub fn cli() -> Command {
+    Command::new("tags")
+        .about("List every tag with how often and when it was used.")
+        .arg(
+            Arg::new("json")
+                .long("json")
+                .num_args(0)
+                .help("Print the tags as JSON."),
+        )
+}
+
+pub fn exec(config_manager: ConfigManager, args: &ArgMatches) -> CliResult {
+    let diary = Diary::from_config(config_manager.config())?;
+    let summaries = tags(&diary)?;
+
+    if args.get_flag("json") {
+        let json = serde_json::to_string_pretty(&summaries)?;
+        println!("{}", json);
+        return Ok(());
+    }
+
+    let width = summaries
+        .iter()
+        .map(|summary| summary.tag.chars().count())
+        .max()
+        .unwrap_or_default();
+    for summary in &summaries {
+        println!(
+            "{:width$}  {:>4}  {} to {}",
+            summary.tag,
+            summary.count,
+            summary.first,
+            summary.last,
+            width = width
+        );
+    }
+    Ok(())
+}
@@ -1,10 +1,25 @@
 #![allow(clippy::missing_const_for_fn)]
-use std::path::PathBuf;
+use std::{collections::BTreeMap, path::PathBuf};
+
+use crate::errors::DiaryError;
 
 pub struct ConfigBuilder {
     diary_path: PathBuf,
     prefix: String,
     file_type: String,
+    title_format: Option<String>,
+    tag_format: Option<String>,
+    template: Option<String>,
+    locale: Option<String>,
+    ordinal_style: Option<String>,
+    layout: Option<String>,
+    week_start: Option<String>,
+    book_title: Option<String>,
+    author: Option<String>,
+    timestamp_adds: bool,
+    front_matter: bool,
+    default: Option<String>,
+    diaries: BTreeMap<String, Config>,
 }
 
 impl ConfigBuilder {
@@ -13,6 +28,19 @@ impl ConfigBuilder {
             diary_path: PathBuf::from(""),
             prefix: "diary".to_owned(),
             file_type: "md".to_owned(),
+            title_format: None,
+            tag_format: None,
+            template: None,
+            locale: None,
+            ordinal_style: None,
+            layout: None,
+            week_start: None,
+            book_title: None,
+            author: None,
+            timestamp_adds: false,
+            front_matter: false,
+            default: None,
+            diaries: BTreeMap::new(),
         }
     }
 
@@ -31,27 +59,193 @@ impl ConfigBuilder {
         self.file_type = file_type.into();
         self
     }
+    #[must_use]
+    pub fn title_format(mut self, title_format: impl Into<String>) -> Self {
+        self.title_format = Some(title_format.into());
+        self
+    }
+    #[must_use]
+    pub fn tag_format(mut self, tag_format: impl Into<String>) -> Self {
+        self.tag_format = Some(tag_format.into());
+        self
+    }
+    #[must_use]
+    pub fn template(mut self, template: impl Into<String>) -> Self {
+        self.template = Some(template.into());
+        self
+    }
+    #[must_use]
+    pub fn locale(mut self, locale: impl Into<String>) -> Self {
+        self.locale = Some(locale.into());
+        self
+    }
+    #[must_use]
+    pub fn ordinal_style(mut self, ordinal_style: impl Into<String>) -> Self {
+        self.ordinal_style = Some(ordinal_style.into());
+        self
+    }
+    #[must_use]
+    pub fn layout(mut self, layout: impl Into<String>) -> Self {
+        self.layout = Some(layout.into());
+        self
+    }
+    #[must_use]
+    pub fn week_start(mut self, week_start: impl Into<String>) -> Self {
+        self.week_start = Some(week_start.into());
+        self
+    }
+    #[must_use]
+    pub fn book_title(mut self, book_title: impl Into<String>) -> Self {
+        self.book_title = Some(book_title.into());
+        self
+    }
+    #[must_use]
+    pub fn author(mut self, author: impl Into<String>) -> Self {
+        self.author = Some(author.into());
+        self
+    }
+    #[must_use]
+    pub fn timestamp_adds(mut self, timestamp_adds: bool) -> Self {
+        self.timestamp_adds = timestamp_adds;
+        self
+    }
+    #[must_use]
+    pub fn front_matter(mut self, front_matter: bool) -> Self {
+        self.front_matter = front_matter;
+        self
+    }
+    #[must_use]
+    pub fn default_diary(mut self, name: impl Into<String>) -> Self {
+        self.default = Some(name.into());
+        self
+    }
+    #[must_use]
+    pub fn diary(mut self, name: impl Into<String>, config: Config) -> Self {
+        self.diaries.insert(name.into(), config);
+        self
+    }
 
     pub fn build(self) -> Config {
         let Self {
             diary_path,
             prefix,
             file_type,
+            title_format,
+            tag_format,
+            template,
+            locale,
+            ordinal_style,
+            layout,
+            week_start,
+            book_title,
+            author,
+            timestamp_adds,
+            front_matter,
+            default,
+            diaries,
         } = self;
         Config {
             diary_path,
             prefix,
             file_type,
+            title_format,
+            tag_format,
+            template,
+            locale,
+            ordinal_style,
+            layout,
+            week_start,
+            book_title,
+            author,
+            timestamp_adds,
+            front_matter,
+            default,
+            diaries,
+            selected: None,
+        }
+    }
+}
+
+impl From<Config> for ConfigBuilder {
+    /// Creates a builder from an existing config, so individual options can be changed.
+    fn from(config: Config) -> Self {
+        let Config {
+            diary_path,
+            prefix,
+            file_type,
+            title_format,
+            tag_format,
+            template,
+            locale,
+            ordinal_style,
+            layout,
+            week_start,
+            book_title,
+            author,
+            timestamp_adds,
+            front_matter,
+            default,
+            diaries,
+            selected: _,
+        } = config;
+        Self {
+            diary_path,
+            prefix,
+            file_type,
+            title_format,
+            tag_format,
+            template,
+            locale,
+            ordinal_style,
+            layout,
+            week_start,
+            book_title,
+            author,
+            timestamp_adds,
+            front_matter,
+            default,
+            diaries,
         }
     }
 }
 
 /// A representation of the cli-diary config file.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
     diary_path: PathBuf,
     prefix: String,
     file_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    title_format: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tag_format: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    template: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    locale: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    ordinal_style: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    layout: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    week_start: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    book_title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    author: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    timestamp_adds: bool,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    front_matter: bool,
+    /// The diary used when none is selected with `--diary`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    default: Option<String>,
+    /// Further diaries, by name. Each is configured like the top level.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    diaries: BTreeMap<String, Self>,
+    /// The diary selected at runtime with `--diary`.
+    #[serde(skip)]
+    selected: Option<String>,
 }
 
 impl Config {
@@ -70,6 +264,106 @@ impl Config {
     pub const fn file_type(&self) -> &String {
         &self.file_type
     }
+
+    pub const fn title_format(&self) -> &Option<String> {
+        &self.title_format
+    }
+
+    pub const fn tag_format(&self) -> &Option<String> {
+        &self.tag_format
+    }
+
+    pub const fn template(&self) -> &Option<String> {
+        &self.template
+    }
+
+    pub const fn locale(&self) -> &Option<String> {
+        &self.locale
+    }
+
+    pub const fn ordinal_style(&self) -> &Option<String> {
+        &self.ordinal_style
+    }
+
+    pub const fn layout(&self) -> &Option<String> {
+        &self.layout
+    }
+
+    pub const fn week_start(&self) -> &Option<String> {
+        &self.week_start
+    }
+
+    pub const fn book_title(&self) -> &Option<String> {
+        &self.book_title
+    }
+
+    pub const fn author(&self) -> &Option<String> {
+        &self.author
+    }
+
+    pub const fn timestamp_adds(&self) -> bool {
+        self.timestamp_adds
+    }
+
+    pub const fn front_matter(&self) -> bool {
+        self.front_matter
+    }
+
+    pub const fn default_diary(&self) -> &Option<String> {
+        &self.default
+    }
+
+    pub const fn diaries(&self) -> &BTreeMap<String, Self> {
+        &self.diaries
+    }
+
+    /// The name of the selected diary, chosen with `--diary` or the `default` key.
+    pub fn selected_name(&self) -> Option<&String> {
+        self.selected.as_ref().or(self.default.as_ref())
+    }
+
+    /// The config of the selected diary, or of the top level if none is selected.
+    ///
+    /// # Returns
+    ///
+    /// The config or a DiaryError if the selected diary is not in the config.
+    pub fn selected(&self) -> Result<&Self, DiaryError> {
+        self.selected_name().map_or(Ok(self), |name| {
+            self.diaries
+                .get(name)
+                .ok_or_else(|| DiaryError::UnknownDiary { name: name.clone() })
+        })
+    }
+
+    /// Replaces the config of the selected diary, or the top level if none is selected.
+    pub fn update_selected(
+        mut self,
+        update: impl FnOnce(Self) -> Self,
+    ) -> Result<Self, DiaryError> {
+        match self.selected_name().cloned() {
+            Some(name) => {
+                let diary = self
+                    .diaries
+                    .remove(&name)
+                    .ok_or_else(|| DiaryError::UnknownDiary { name: name.clone() })?;
+                self.diaries.insert(name, update(diary));
+                Ok(self)
+            }
+            None => Ok(update(self)),
+        }
+    }
+
+    /// Adds a diary to the config, or replaces the diary of the same name. The new
+    /// diary becomes the default if the top level has no diary of its own.
+    #[must_use]
+    pub fn with_diary(mut self, name: impl Into<String>, diary: Self) -> Self {
+        let name = name.into();
+        if self.diary_path.as_os_str().is_empty() && self.default.is_none() {
+            self.default = Some(name.clone());
+        }
+        self.diaries.insert(name, diary);
+        self
+    }
 }
 
 impl Default for Config {
@@ -118,6 +412,15 @@ impl ConfigManager {
         }
     }
 
+    /// Selects one of the diaries in the config by name, used by the `--diary` flag.
+    #[must_use]
+    pub fn with_diary(mut self, name: Option<String>) -> Self {
+        if name.is_some() {
+            self.config.selected = name;
+        }
+        self
+    }
+
     #[must_use]
     pub fn update_config(mut self, config: Config) -> Self {
         self.config = config;
@@ -129,7 +432,7 @@ impl ConfigManager {
 mod tests {
     use std::path::PathBuf;
 
-    use super::{Config, ConfigManager};
+    use super::{Config, ConfigBuilder, ConfigManager};
 
     #[test]
     fn full_config_build() {
@@ -142,6 +445,58 @@ mod tests {
         assert_eq!(cfg.diary_path(), &PathBuf::from("/home/"))
     }
 
+    #[test]
+    fn config_to_builder() {
+        let cfg = Config::builder().prefix("dy").locale("fr").build();
+
+        let cfg = ConfigBuilder::from(cfg).file_type("rst").build();
+
+        assert_eq!(cfg.prefix(), "dy");
+        assert_eq!(cfg.file_type(), "rst");
+        assert_eq!(cfg.locale().as_deref(), Some("fr"))
+    }
+
+    #[test]
+    fn select_diary() {
+        let work = Config::builder().prefix("log").build();
+        let cfg = Config::builder().prefix("dy").diary("work", work).build();
+
+        assert_eq!(cfg.selected().unwrap().prefix(), "dy");
+
+        let cfg = ConfigBuilder::from(cfg).default_diary("work").build();
+
+        assert_eq!(cfg.selected().unwrap().prefix(), "log");
+
+        let cfg = ConfigManager::default()
+            .update_config(cfg)
+            .with_diary(Some("home".to_owned()));
+
+        assert!(cfg.config().selected().is_err());
+    }
+
+    #[test]
+    fn update_selected_diary() {
+        let cfg = Config::builder()
+            .diary("work", Config::default())
+            .default_diary("work")
+            .build();
+
+        let cfg = cfg
+            .update_selected(|work| ConfigBuilder::from(work).file_type("rst").build())
+            .unwrap();
+
+        assert_eq!(cfg.file_type(), "md");
+        assert_eq!(cfg.diaries()["work"].file_type(), "rst");
+    }
+
+    #[test]
+    fn add_diary() {
+        let cfg = Config::default().with_diary("work", Config::builder().prefix("log").build());
+
+        assert_eq!(cfg.default_diary().as_deref(), Some("work"));
+        assert_eq!(cfg.selected().unwrap().prefix(), "log");
+    }
+
     #[test]
     fn config_manager_with_location() {
         let location = Some(PathBuf::from("/tmp/"));
@@ -0,0 +1,313 @@
+//! # Entries
+//!
+//! A structured model of a diary entry, as read back from an entry file.
+use chrono::prelude::*;
+
+/// The text written below a tag in an entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section {
+    /// The tag heading the section, empty for an untagged timestamp.
+    pub tag: String,
+    /// The time the section was added, if it was timestamped.
+    pub time: Option<NaiveTime>,
+    /// The text of the section, without surrounding blank lines.
+    pub body: String,
+}
+
+impl Section {
+    pub fn new(tag: impl Into<String>, body: impl Into<String>) -> Self {
+        Self {
+            tag: tag.into(),
+            time: None,
+            body: body.into(),
+        }
+    }
+
+    #[must_use]
+    pub const fn with_time(mut self, time: NaiveTime) -> Self {
+        self.time = Some(time);
+        self
+    }
+
+    /// The number of words written in the body of the section.
+    pub fn word_count(&self) -> usize {
+        self.body.split_whitespace().count()
+    }
+}
+
+/// The metadata block written before the title of an entry, e.g. YAML front matter in markdown.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrontMatter {
+    /// The metadata fields other than the tags, in the order they are written.
+    pub fields: Vec<(String, String)>,
+    /// The tags used in the entry.
+    pub tags: Vec<String>,
+}
+
+impl FrontMatter {
+    /// The front matter of a new entry, with an empty tag list and a word count placeholder.
+    pub fn new(date: &DateTime<Local>, weekday: &str) -> Self {
+        Self {
+            fields: vec![
+                ("date".to_owned(), date.format("%Y-%m-%d").to_string()),
+                ("weekday".to_owned(), weekday.to_owned()),
+                ("words".to_owned(), "0".to_owned()),
+            ],
+            tags: vec![],
+        }
+    }
+
+    /// The value of the given field, if present.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(name, _)| name == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Adds a tag to the tag list, returning false if it was already listed.
+    pub fn add_tag(&mut self, tag: &str) -> bool {
+        if self.tags.iter().any(|t| t == tag) {
+            return false;
+        }
+        self.tags.push(tag.to_owned());
+        true
+    }
+}
+
+/// A diary entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// The date of the entry.
+    pub date: DateTime<Local>,
+    /// The metadata block before the title, if the entry has one.
+    pub front_matter: Option<FrontMatter>,
+    /// The text of the title, including any markup such as superscript.
+    pub title: String,
+    /// The free text written before the first tag.
+    pub preamble: String,
+    /// The tagged sections, in the order they appear in the entry.
+    pub sections: Vec<Section>,
+}
+
+impl Entry {
+    pub fn new(date: DateTime<Local>, title: impl Into<String>) -> Self {
+        Self {
+            date,
+            front_matter: None,
+            title: title.into(),
+            preamble: String::new(),
+            sections: vec![],
+        }
+    }
+
+    /// The distinct tags of the entry, in the order they first appear.
+    pub fn tags(&self) -> Vec<&str> {
+        let mut tags: Vec<&str> = vec![];
+        for section in &self.sections {
+            if !section.tag.is_empty() && !tags.contains(&section.tag.as_str()) {
+                tags.push(&section.tag);
+            }
+        }
+        tags
+    }
+
+    /// The sections written under the given tag.
+    pub fn sections_tagged<'a>(&'a self, tag: &'a str) -> impl Iterator<Item = &'a Section> {
+        self.sections
+            .iter()
+            .filter(move |section| section.tag == tag)
+    }
+
+    /// The number of words written in the preamble and sections, leaving out the
+    /// title, headings and front matter.
+    pub fn word_count(&self) -> usize {
+        let sections: usize = self.sections.iter().map(Section::word_count).sum();
+        self.preamble.split_whitespace().count() + sections
+    }
+
+    /// Adds a line of text to the last section, or the preamble if there are no sections.
+    pub(crate) fn push_line(&mut self, line: &str) {
+        let text = match self.sections.last_mut() {
+            Some(section) => &mut section.body,
+            None => &mut self.preamble,
+        };
+        text.push_str(line);
+        text.push('\n');
+    }
+
+    /// Removes the blank lines surrounding the preamble and section bodies.
+    pub(crate) fn trim(mut self) -> Self {
+        self.preamble = trim_blank_lines(&self.preamble);
+        for section in &mut self.sections {
+            section.body = trim_blank_lines(&section.body);
+        }
+        self
+    }
+}
+
+fn trim_blank_lines(text: &str) -> String {
+    let lines: Vec<&str> = text.lines().collect();
+    let start = lines.iter().position(|line| !line.trim().is_empty());
+    let end = lines.iter().rposition(|line| !line.trim().is_empty());
+
+    match (start, end) {
+        (Some(start), Some(end)) => lines[start..=end].join("\n"),
+        _ => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use chrono::prelude::*;
+
+    use super::{Entry, FrontMatter, Section};
+    use crate::{utils::date::OrdinalStyle, EntryContent, EntryFileType, MarkdownDiary, RstDiary};
+
+    fn test_entry() -> Entry {
+        let date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+        let mut entry = Entry::new(date, "Saturday 6<sup>th</sup> November 2021");
+        entry.preamble = "Some thoughts.\n\nOver two paragraphs.".to_owned();
+        entry.sections = vec![
+            Section::new("Meeting", "Notes from the meeting."),
+            Section::new("Tip", "A tip.").with_time(NaiveTime::from_hms_opt(14, 32, 0).unwrap()),
+            Section::new("Meeting", "Another meeting."),
+        ];
+        entry
+    }
+
+    #[test]
+    fn parse_md() {
+        let entry_file = MarkdownDiary {};
+        let date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+
+        let content = entry_file.title(&date, OrdinalStyle::Superscript)
+            + "Some thoughts.\n\nOver two paragraphs.\n"
+            + &entry_file.tag("Meeting".to_owned())
+            + "Notes from the meeting.\n"
+            + &entry_file.timestamp(
+                &NaiveTime::from_hms_opt(14, 32, 0).unwrap(),
+                Some("Tip".to_owned()),
+            )
+            + "A tip.\n"
+            + &entry_file.tag("Meeting".to_owned())
+            + "Another meeting.\n";
+
+        assert_eq!(entry_file.parse(&date, &content), test_entry())
+    }
+
+    #[test]
+    fn parse_rst() {
+        let entry_file = RstDiary {};
+        let date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+
+        let content = "Saturday 6\\ :sup:`th` November 2021\n===================================\n\nMeeting\n^^^^^^^\n\nNotes.\n\n";
+
+        let entry = entry_file.parse(&date, content);
+
+        assert_eq!(entry.title, "Saturday 6\\ :sup:`th` November 2021");
+        assert_eq!(entry.preamble, "");
+        assert_eq!(entry.sections, vec![Section::new("Meeting", "Notes.")]);
+    }
+
+    #[test]
+    fn parse_untagged_timestamp() {
+        let entry_file = MarkdownDiary {};
+        let date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+
+        let content = "# Title\n\n## Meeting\n\nNotes.\n\n### 09:15\n\nMore notes.\n";
+
+        let entry = entry_file.parse(&date, content);
+
+        assert_eq!(
+            entry.sections,
+            vec![
+                Section::new("Meeting", "Notes."),
+                Section::new("", "More notes.")
+                    .with_time(NaiveTime::from_hms_opt(9, 15, 0).unwrap()),
+            ]
+        );
+        assert_eq!(entry.tags(), vec!["Meeting"]);
+        assert_eq!(entry_file.parse(&date, &entry_file.render(&entry)), entry);
+    }
+
+    #[test]
+    fn round_trip() {
+        for file_type in ["md", "rst", "org", "adoc", "txt"] {
+            let entry_file = EntryFileType::from_str(file_type).unwrap();
+            let entry = test_entry();
+
+            let content = entry_file.render(&entry);
+
+            assert_eq!(entry_file.parse(&entry.date, &content), entry);
+            assert_eq!(
+                entry_file.render(&entry_file.parse(&entry.date, &content)),
+                content
+            );
+        }
+    }
+
+    #[test]
+    fn round_trip_front_matter() {
+        for file_type in ["md", "rst", "org", "adoc", "txt"] {
+            let entry_file = EntryFileType::from_str(file_type).unwrap();
+            let mut entry = test_entry();
+            let mut front_matter = FrontMatter::new(&entry.date, "Saturday");
+            front_matter.add_tag("Meeting");
+            front_matter.add_tag("Tip");
+            entry.front_matter = Some(front_matter);
+
+            let content = entry_file.render(&entry);
+
+            assert_eq!(entry_file.parse(&entry.date, &content), entry);
+        }
+    }
+
+    #[test]
+    fn parse_md_front_matter() {
+        let entry_file = MarkdownDiary {};
+        let date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+
+        let content =
+            "---\ndate: 2021-11-06\ntags: [Meeting, Tip]\nmood:\n---\n\n# Title\n\nText.\n";
+
+        let entry = entry_file.parse(&date, content);
+        let front_matter = entry.front_matter.unwrap();
+
+        assert_eq!(front_matter.get("date"), Some("2021-11-06"));
+        assert_eq!(front_matter.get("mood"), Some(""));
+        assert_eq!(front_matter.tags, vec!["Meeting", "Tip"]);
+        assert_eq!(entry.title, "Title");
+        assert_eq!(entry.preamble, "Text.");
+    }
+
+    #[test]
+    fn parse_txt_without_front_matter() {
+        let entry_file = EntryFileType::from_str("txt").unwrap();
+        let date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+
+        let content = "Note: call the bank.\n\nText.\n";
+
+        let entry = entry_file.parse(&date, content);
+
+        assert_eq!(entry.front_matter, None);
+        assert_eq!(entry.preamble, "Note: call the bank.\n\nText.");
+    }
+
+    #[test]
+    fn entry_tags() {
+        let entry = test_entry();
+
+        assert_eq!(entry.tags(), vec!["Meeting", "Tip"]);
+        assert_eq!(entry.sections_tagged("Meeting").count(), 2);
+    }
+
+    #[test]
+    fn entry_word_count() {
+        let entry = test_entry();
+
+        assert_eq!(entry.word_count(), 13);
+    }
+}
@@ -1,4 +1,4 @@
-use std::io;
+use std::{io, path::PathBuf};
 
 use chrono::ParseError;
 use thiserror::Error;
@@ -9,7 +9,7 @@ pub struct CliError {
 }
 
 impl CliError {
-    pub fn new(error: anyhow::Error, code: i32) -> Self {
+    pub const fn new(error: anyhow::Error, code: i32) -> Self {
         Self {
             error: Some(error),
             exit_code: code,
@@ -53,6 +53,12 @@ impl From<DiaryError> for CliError {
     }
 }
 
+impl From<serde_json::Error> for CliError {
+    fn from(err: serde_json::Error) -> Self {
+        DiaryError::from(err).into()
+    }
+}
+
 impl From<ParseError> for CliError {
     fn from(err: ParseError) -> Self {
         Self::new(err.into(), 101)
@@ -88,6 +94,54 @@ pub enum DiaryError {
     #[error("Unsupported file type.")]
     BadFileType,
 
+    #[error("Unsupported ordinal style `{style}`, use one of superscript, inline or none.")]
+    BadOrdinalStyle { style: String },
+
+    #[error("Unknown day of the week `{weekday}`.")]
+    BadWeekday { weekday: String },
+
+    #[error("Unsupported locale `{locale}`.")]
+    BadLocale { locale: String },
+
+    #[error("The template `{name}` was not found in the templates folder.")]
+    NoTemplate { name: String },
+
+    #[error("Invalid format `{format}`: {reason}.")]
+    BadFormat { format: String, reason: String },
+
+    #[error("Invalid search pattern `{pattern}`: {reason}.")]
+    BadPattern { pattern: String, reason: String },
+
+    #[error("Invalid date range: {reason}.")]
+    BadDateRange { reason: String },
+
+    #[error("There is no diary called `{name}` in the config.")]
+    UnknownDiary { name: String },
+
+    #[error("No entries have sections tagged `{tag}`.")]
+    UnknownTag { tag: String },
+
+    #[error("Invalid entry name `{name}`, use only letters, numbers and dashes.")]
+    BadEntryName { name: String },
+
+    #[error("Cannot move an entry to `{}`, another entry is already there.", path.display())]
+    EntryClash { path: PathBuf },
+
+    #[error("An entry already exists at `{}`, pass --merge to add to it.", path.display())]
+    ImportClash { path: PathBuf },
+
+    #[error("Invalid import: {reason}.")]
+    BadImport { reason: String },
+
     #[error(transparent)]
     GitError(#[from] git2::Error),
+
+    #[error(transparent)]
+    JsonError(#[from] serde_json::Error),
+
+    #[error(transparent)]
+    CsvError(#[from] csv::Error),
+
+    #[error(transparent)]
+    ZipError(#[from] zip::result::ZipError),
 }
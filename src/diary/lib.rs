@@ -7,6 +7,7 @@ extern crate serde_derive;
 extern crate serde;
 
 pub mod config;
+pub mod entry;
 pub mod errors;
 pub mod ops;
 pub mod utils;
@@ -25,8 +26,15 @@ use enum_dispatch::enum_dispatch;
 
 use crate::{
     config::Config,
+    entry::{Entry, FrontMatter, Section},
     errors::DiaryError,
-    utils::{date, file_system},
+    utils::{
+        date::{self, OrdinalStyle},
+        file_system,
+        format::{FormatString, DATE_PLACEHOLDERS, TAG_PLACEHOLDERS},
+        layout::{Layout, DEFAULT_LAYOUT},
+        locale::Locale,
+    },
 };
 
 fn title_elements(date: DateTime<Local>) -> (String, String, String) {
@@ -37,15 +45,304 @@ fn title_elements(date: DateTime<Local>) -> (String, String, String) {
     (start_title, date_superscript, end_title)
 }
 
+/// Underlines a heading, as rst and plain text headings are written.
+pub(crate) fn underline(text: &str, character: char) -> String {
+    let underline: String = text.chars().map(|_| character).collect();
+
+    format!("{}\n{}\n\n", text, underline)
+}
+
+/// Reads the time and tag from the text of a timestamp heading, e.g. `14:32 — Meeting`.
+/// The tag is empty for an untagged timestamp such as `14:32`.
+pub(crate) fn read_timestamp(text: &str) -> Option<(NaiveTime, &str)> {
+    let (time, tag) = text.split_once(" — ").unwrap_or((text, ""));
+    let time = NaiveTime::parse_from_str(time, "%H:%M").ok()?;
+
+    Some((time, tag))
+}
+
+/// Replaces each superscript in the given text, found using the markup of a
+/// superscript around `\0`.
+///
+/// # Arguments
+///
+/// * `marked` - The superscript markup of `\0` in the file type, e.g. `<sup>\0</sup>`.
+/// * `text` - The text to replace the superscripts of.
+/// * `replace` - Gives the replacement of the text inside a superscript.
+pub(crate) fn replace_superscripts(
+    marked: String,
+    text: &str,
+    replace: impl Fn(&str) -> String,
+) -> String {
+    let (open, close) = marked.split_once('\0').unwrap_or_default();
+    if open.is_empty() {
+        return text.to_owned();
+    }
+
+    let mut replaced = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find(open) {
+        let inner = &rest[start + open.len()..];
+        let Some(end) = inner.find(close) else {
+            break;
+        };
+        replaced.push_str(&rest[..start]);
+        replaced.push_str(&replace(&inner[..end]));
+        rest = &inner[end + close.len()..];
+    }
+    replaced.push_str(rest);
+    replaced
+}
+
+/// Reads a heading made of a repeated marker character, e.g. `## Tag` in markdown.
+fn read_marked_heading(lines: &[&str], marker: char) -> Option<(String, usize, usize)> {
+    let line = lines.first()?;
+    let level = line.chars().take_while(|c| *c == marker).count();
+    let text = line[level..].strip_prefix(' ')?.trim();
+
+    (level > 0 && !text.is_empty()).then(|| (text.to_owned(), level, 1))
+}
+
+/// Reads a heading underlined with one of the given characters, e.g. rst headings.
+fn read_underlined_heading(
+    lines: &[&str],
+    levels: &[(char, usize)],
+) -> Option<(String, usize, usize)> {
+    let (text, underline) = (lines.first()?.trim_end(), lines.get(1)?.trim_end());
+    let character = underline.chars().next()?;
+    let (_, level) = levels.iter().find(|(c, _)| *c == character)?;
+
+    let is_underline = underline.chars().all(|c| c == character)
+        && underline.chars().count() >= text.chars().count();
+
+    (is_underline && !text.trim().is_empty()).then(|| (text.trim().to_owned(), *level, 2))
+}
+
+/// Writes front matter as `key: value` lines, each starting with the given marker,
+/// e.g. `:date: 2021-11-06` in rst.
+fn write_fields(front_matter: &FrontMatter, marker: &str, tags: &str) -> String {
+    let field = |key: &str, value: &str| match value {
+        "" => format!("{}{}:\n", marker, key),
+        _ => format!("{}{}: {}\n", marker, key, value),
+    };
+    let mut fields: String = front_matter
+        .fields
+        .iter()
+        .map(|(key, value)| field(key, value))
+        .collect();
+    fields.push_str(&field("tags", tags));
+    fields
+}
+
+/// Reads a `key: value` line of front matter, the inverse of `write_fields`.
+fn read_field<'a>(line: &'a str, marker: &str) -> Option<(&'a str, &'a str)> {
+    let line = line.trim_end().strip_prefix(marker)?;
+    let (key, value) = line
+        .split_once(": ")
+        .or_else(|| line.strip_suffix(':').map(|key| (key, "")))?;
+    let is_key = !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+
+    is_key.then(|| (key, value.trim()))
+}
+
+/// Reads the `key: value` lines at the start of the given lines into front matter.
+///
+/// # Returns
+///
+/// The front matter and the number of lines read.
+fn read_fields(lines: &[&str], marker: &str) -> (FrontMatter, usize) {
+    let mut front_matter = FrontMatter {
+        fields: vec![],
+        tags: vec![],
+    };
+    let mut count = 0;
+    for (key, value) in lines.iter().map_while(|line| read_field(line, marker)) {
+        if key == "tags" {
+            front_matter.tags = value
+                .trim_start_matches('[')
+                .trim_end_matches(']')
+                .split(',')
+                .map(str::trim)
+                .filter(|tag| !tag.is_empty())
+                .map(str::to_owned)
+                .collect();
+        } else {
+            front_matter.fields.push((key.to_owned(), value.to_owned()));
+        }
+        count += 1;
+    }
+    (front_matter, count)
+}
+
+/// Reads front matter written as a list of fields followed by a blank line, e.g. in rst.
+fn read_field_list(lines: &[&str], marker: &str) -> Option<(FrontMatter, usize)> {
+    let (front_matter, count) = read_fields(lines, marker);
+    let blank = lines.get(count).map(|line| line.trim().is_empty());
+
+    (count > 0 && blank != Some(false))
+        .then(|| (front_matter, count + usize::from(blank.is_some())))
+}
+
+/// Reads front matter written as fields between two `---` lines, e.g. YAML in markdown.
+fn read_delimited_fields(lines: &[&str]) -> Option<(FrontMatter, usize)> {
+    if lines.first()?.trim_end() != "---" {
+        return None;
+    }
+    let end = lines
+        .iter()
+        .skip(1)
+        .position(|line| line.trim_end() == "---")?
+        + 1;
+    let (front_matter, count) = read_fields(&lines[1..end], "");
+
+    (count == end - 1).then(|| (front_matter, with_blank_line(lines, end + 1)))
+}
+
+/// Skips the blank line after a block spanning `span` lines, if there is one.
+fn with_blank_line(lines: &[&str], span: usize) -> usize {
+    span + usize::from(lines.get(span).is_some_and(|line| line.trim().is_empty()))
+}
+
 #[enum_dispatch]
 pub trait EntryContent {
     fn extension(&self) -> &'static str;
 
-    fn title(&self, date: &DateTime<Local>) -> String;
+    /// Marks up the given text as superscript, e.g. the ordinal suffix in a title.
+    fn superscript(&self, text: &str) -> String;
+
+    /// Marks up the given text as a heading. Level 1 is used for entry titles and
+    /// level 2 for tags.
+    fn heading(&self, text: &str, level: usize) -> String;
+
+    /// The inverse of `heading`. Reads a heading from the start of the given lines.
+    ///
+    /// # Returns
+    ///
+    /// The text and level of the heading along with the number of lines it spans,
+    /// or None if the lines do not start with a heading.
+    fn read_heading(&self, lines: &[&str]) -> Option<(String, usize, usize)>;
+
+    /// Marks up the metadata block written before the title when front matter is enabled.
+    fn front_matter(&self, front_matter: &FrontMatter) -> String;
+
+    /// The inverse of `front_matter`. Reads front matter from the start of the given lines.
+    ///
+    /// # Returns
+    ///
+    /// The front matter along with the number of lines it spans, including the blank
+    /// line after it, or None if the lines do not start with front matter.
+    fn read_front_matter(&self, lines: &[&str]) -> Option<(FrontMatter, usize)>;
+
+    /// The English title of the entry for the given date, writing the ordinal suffix
+    /// in the given style.
+    fn title(&self, date: &DateTime<Local>, style: OrdinalStyle) -> String {
+        let (start_title, date_superscript, end_title) = title_elements(*date);
+        let ordinal = match style {
+            OrdinalStyle::Superscript => self.superscript(&date_superscript),
+            OrdinalStyle::Inline => date_superscript,
+            OrdinalStyle::None => String::new(),
+        };
+
+        let title = format!("{}{} {}", start_title, ordinal, end_title);
+
+        self.heading(&title, 1)
+    }
+
+    /// The inverse of `superscript`. Removes superscript markup from the given text,
+    /// e.g. `6<sup>th</sup>` becomes `6th` in markdown.
+    fn strip_superscript(&self, text: &str) -> String {
+        replace_superscripts(self.superscript("\0"), text, str::to_owned)
+    }
+
+    fn tag(&self, tag_name: String) -> String {
+        self.heading(&tag_name, 2)
+    }
+
+    /// A heading marking the time content was added, e.g. `### 14:32 — Meeting` in markdown.
+    fn timestamp(&self, time: &NaiveTime, tag_name: Option<String>) -> String {
+        let time = time.format("%H:%M");
+        let text = tag_name.map_or_else(
+            || time.to_string(),
+            |tag_name| format!("{} — {}", time, tag_name),
+        );
+
+        self.heading(&text, 3)
+    }
+
+    /// The inverse of `title`, `tag` and `timestamp`. Reads the content of an entry into an Entry.
+    ///
+    /// # Arguments
+    ///
+    /// * `date` - The date of the entry, usually taken from its file name.
+    /// * `content` - The content of the entry file.
+    fn parse(&self, date: &DateTime<Local>, content: &str) -> Entry {
+        let lines: Vec<&str> = content.lines().collect();
+        let mut entry = Entry::new(*date, "");
+        let mut i = 0;
 
-    fn tag(&self, tag_name: String) -> String;
+        if let Some((front_matter, span)) = self.read_front_matter(&lines) {
+            entry.front_matter = Some(front_matter);
+            i += span;
+        }
+
+        while i < lines.len() {
+            match self.read_heading(&lines[i..]) {
+                Some((text, 1, span)) if entry.title.is_empty() && entry.sections.is_empty() => {
+                    entry.title = text;
+                    i += span;
+                }
+                Some((text, 2, span)) => {
+                    entry.sections.push(Section::new(text, ""));
+                    i += span;
+                }
+                Some((text, 3, span)) if read_timestamp(&text).is_some() => {
+                    let (time, tag) = read_timestamp(&text).unwrap();
+                    entry.sections.push(Section::new(tag, "").with_time(time));
+                    i += span;
+                }
+                _ => {
+                    entry.push_line(lines[i]);
+                    i += 1;
+                }
+            }
+        }
+        entry.trim()
+    }
+
+    /// Writes an Entry out as content of this file type.
+    fn render(&self, entry: &Entry) -> String {
+        let mut content = entry
+            .front_matter
+            .as_ref()
+            .map(|front_matter| self.front_matter(front_matter))
+            .unwrap_or_default();
+        content.push_str(&self.heading(&entry.title, 1));
+        if !entry.preamble.is_empty() {
+            content.push_str(&entry.preamble);
+            content.push_str("\n\n");
+        }
+        for section in &entry.sections {
+            let heading = section.time.map_or_else(
+                || self.tag(section.tag.clone()),
+                |time| {
+                    let tag = Some(section.tag.clone()).filter(|tag| !tag.is_empty());
+                    self.timestamp(&time, tag)
+                },
+            );
+            content.push_str(&heading);
+            if !section.body.is_empty() {
+                content.push_str(&section.body);
+                content.push_str("\n\n");
+            }
+        }
+        content
+    }
 }
 
+#[derive(Clone)]
 pub struct MarkdownDiary {}
 
 impl EntryContent for MarkdownDiary {
@@ -53,19 +350,28 @@ impl EntryContent for MarkdownDiary {
         "md"
     }
 
-    fn title(&self, date: &DateTime<Local>) -> String {
-        let (start_title, date_superscript, end_title) = title_elements(*date);
+    fn superscript(&self, text: &str) -> String {
+        format!("<sup>{}</sup>", text)
+    }
 
-        format!(
-            "# {}<sup>{}</sup> {}\n\n",
-            start_title, date_superscript, end_title
-        )
+    fn heading(&self, text: &str, level: usize) -> String {
+        format!("{} {}\n\n", "#".repeat(level), text)
     }
 
-    fn tag(&self, tag_name: String) -> String {
-        format!("## {}\n\n", tag_name)
+    fn read_heading(&self, lines: &[&str]) -> Option<(String, usize, usize)> {
+        read_marked_heading(lines, '#').filter(|(_, level, _)| *level <= 6)
+    }
+
+    fn front_matter(&self, front_matter: &FrontMatter) -> String {
+        let tags = format!("[{}]", front_matter.tags.join(", "));
+        format!("---\n{}---\n\n", write_fields(front_matter, "", &tags))
+    }
+
+    fn read_front_matter(&self, lines: &[&str]) -> Option<(FrontMatter, usize)> {
+        read_delimited_fields(lines)
     }
 }
+#[derive(Clone)]
 pub struct RstDiary {}
 
 impl EntryContent for RstDiary {
@@ -73,34 +379,152 @@ impl EntryContent for RstDiary {
         "rst"
     }
 
-    fn title(&self, date: &DateTime<Local>) -> String {
-        let (start_title, date_superscript, end_title) = title_elements(*date);
+    fn superscript(&self, text: &str) -> String {
+        format!("\\ :sup:`{}`", text)
+    }
 
-        let first_line = format!(
-            "{}\\ :sup:`{}` {}",
-            start_title, date_superscript, end_title
-        );
-        let first_line_len = first_line.chars().count();
+    fn heading(&self, text: &str, level: usize) -> String {
+        let character = match level {
+            1 => '=',
+            2 => '^',
+            3 => '~',
+            4 => '"',
+            5 => '\'',
+            _ => '.',
+        };
 
-        let second_line = format!("{:=<1$}", "", first_line_len);
+        underline(text, character)
+    }
 
-        format!("{}\n{}\n\n", first_line, second_line)
+    fn read_heading(&self, lines: &[&str]) -> Option<(String, usize, usize)> {
+        read_underlined_heading(
+            lines,
+            &[('=', 1), ('^', 2), ('~', 3), ('"', 4), ('\'', 5), ('.', 6)],
+        )
     }
 
-    fn tag(&self, tag_name: String) -> String {
-        let first_line_len = tag_name.chars().count();
+    fn front_matter(&self, front_matter: &FrontMatter) -> String {
+        write_fields(front_matter, ":", &front_matter.tags.join(", ")) + "\n"
+    }
+
+    fn read_front_matter(&self, lines: &[&str]) -> Option<(FrontMatter, usize)> {
+        read_field_list(lines, ":")
+    }
+}
+
+#[derive(Clone)]
+pub struct OrgDiary {}
+
+impl EntryContent for OrgDiary {
+    fn extension(&self) -> &'static str {
+        "org"
+    }
+
+    fn superscript(&self, text: &str) -> String {
+        format!("^{{{}}}", text)
+    }
+
+    fn heading(&self, text: &str, level: usize) -> String {
+        format!("{} {}\n\n", "*".repeat(level), text)
+    }
+
+    fn read_heading(&self, lines: &[&str]) -> Option<(String, usize, usize)> {
+        read_marked_heading(lines, '*')
+    }
+
+    fn front_matter(&self, front_matter: &FrontMatter) -> String {
+        write_fields(front_matter, "#+", &front_matter.tags.join(", ")) + "\n"
+    }
+
+    fn read_front_matter(&self, lines: &[&str]) -> Option<(FrontMatter, usize)> {
+        read_field_list(lines, "#+")
+    }
+}
+
+#[derive(Clone)]
+pub struct AsciiDocDiary {}
+
+impl EntryContent for AsciiDocDiary {
+    fn extension(&self) -> &'static str {
+        "adoc"
+    }
+
+    fn superscript(&self, text: &str) -> String {
+        format!("^{}^", text)
+    }
+
+    fn heading(&self, text: &str, level: usize) -> String {
+        format!("{} {}\n\n", "=".repeat(level), text)
+    }
+
+    fn read_heading(&self, lines: &[&str]) -> Option<(String, usize, usize)> {
+        read_marked_heading(lines, '=')
+    }
+
+    fn front_matter(&self, front_matter: &FrontMatter) -> String {
+        write_fields(front_matter, ":", &front_matter.tags.join(", ")) + "\n"
+    }
+
+    fn read_front_matter(&self, lines: &[&str]) -> Option<(FrontMatter, usize)> {
+        read_field_list(lines, ":")
+    }
+}
+
+#[derive(Clone)]
+pub struct PlainTextDiary {}
+
+impl EntryContent for PlainTextDiary {
+    fn extension(&self) -> &'static str {
+        "txt"
+    }
+
+    fn superscript(&self, text: &str) -> String {
+        text.to_owned()
+    }
+
+    fn heading(&self, text: &str, level: usize) -> String {
+        match level {
+            1 => underline(text, '='),
+            2 => format!("[{}]\n\n", text),
+            3 => underline(text, '-'),
+            4 => underline(text, '~'),
+            5 => underline(text, '"'),
+            _ => underline(text, '\''),
+        }
+    }
+
+    fn read_heading(&self, lines: &[&str]) -> Option<(String, usize, usize)> {
+        lines
+            .first()
+            .and_then(|line| line.trim_end().strip_prefix('['))
+            .and_then(|line| line.strip_suffix(']'))
+            .filter(|tag| !tag.trim().is_empty())
+            .map(|tag| (tag.trim().to_owned(), 2, 1))
+            .or_else(|| {
+                read_underlined_heading(lines, &[('=', 1), ('-', 3), ('~', 4), ('"', 5), ('\'', 6)])
+            })
+    }
 
-        let second_line = format!("{:^<1$}", "", first_line_len);
+    fn front_matter(&self, front_matter: &FrontMatter) -> String {
+        let fields = write_fields(front_matter, "", &front_matter.tags.join(", "));
+        format!("---\n{}---\n\n", fields)
+    }
 
-        format!("{}\n{}\n\n", tag_name, second_line)
+    fn read_front_matter(&self, lines: &[&str]) -> Option<(FrontMatter, usize)> {
+        // Plain `key: value` lines could be the start of any entry, so they need delimiting.
+        read_delimited_fields(lines)
     }
 }
 
 #[enum_dispatch(EntryContent)]
+#[derive(Clone)]
 #[non_exhaustive]
 pub enum EntryFileType {
     MarkdownDiary,
     RstDiary,
+    OrgDiary,
+    AsciiDocDiary,
+    PlainTextDiary,
 }
 
 impl FromStr for EntryFileType {
@@ -110,6 +534,9 @@ impl FromStr for EntryFileType {
         match s {
             "md" => Ok(MarkdownDiary {}.into()),
             "rst" => Ok(RstDiary {}.into()),
+            "org" => Ok(OrgDiary {}.into()),
+            "adoc" => Ok(AsciiDocDiary {}.into()),
+            "txt" => Ok(PlainTextDiary {}.into()),
             _ => Err(DiaryError::BadFileType),
         }
     }
@@ -125,10 +552,35 @@ pub fn process_file_type(potential_file_type: Option<&str>) -> Result<Option<&st
     )
 }
 
+/// An entry file found in the diary folder.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntryFile {
+    pub date: DateTime<Local>,
+    /// The name of the entry, or None for the main entry of the day.
+    pub name: Option<String>,
+    pub path: PathBuf,
+}
+
+/// Whether the given text can name an entry, i.e. it is made of letters, numbers and dashes.
+pub fn is_entry_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '-')
+}
+
+#[derive(Clone)]
 pub struct Diary {
     prefix: String,
     diary_path: PathBuf,
     file_type: EntryFileType,
+    title_format: Option<FormatString>,
+    tag_format: Option<FormatString>,
+    template: Option<String>,
+    locale: Locale,
+    ordinal_style: OrdinalStyle,
+    layout: Layout,
+    week_start: Weekday,
+    book_title: Option<String>,
+    author: Option<String>,
+    front_matter: bool,
 }
 
 impl Diary {
@@ -141,41 +593,293 @@ impl Diary {
             prefix: prefix.to_owned(),
             diary_path: diary_path.to_path_buf(),
             file_type: entry_file_type,
+            title_format: None,
+            tag_format: None,
+            template: None,
+            locale: Locale::default(),
+            ordinal_style: OrdinalStyle::default(),
+            layout: Layout::parse(DEFAULT_LAYOUT)?,
+            week_start: Weekday::Mon,
+            book_title: None,
+            author: None,
+            front_matter: false,
         }))
     }
 
+    /// Creates the diary selected in the config, see `Config::selected`.
     pub fn from_config(cfg: &Config) -> Result<Box<Self>, DiaryError> {
-        Self::new(cfg.prefix(), cfg.diary_path(), cfg.file_type())
+        let cfg = cfg.selected()?;
+        let mut diary = Self::new(cfg.prefix(), cfg.diary_path(), cfg.file_type())?;
+        diary.locale = cfg
+            .locale()
+            .as_deref()
+            .map_or_else(|| Ok(Locale::default()), Locale::from_str)?;
+        diary.ordinal_style = cfg
+            .ordinal_style()
+            .as_deref()
+            .map_or_else(|| Ok(OrdinalStyle::default()), OrdinalStyle::from_str)?;
+        let title_format = cfg
+            .title_format()
+            .as_deref()
+            .unwrap_or_else(|| diary.locale.title_format());
+        diary.title_format = Some(FormatString::parse(title_format, &DATE_PLACEHOLDERS)?);
+        diary.tag_format = cfg
+            .tag_format()
+            .as_ref()
+            .map(|format| FormatString::parse(format, &TAG_PLACEHOLDERS))
+            .transpose()?;
+        if let Some(layout) = cfg.layout() {
+            diary.layout = Layout::parse(layout)?;
+        }
+        if let Some(week_start) = cfg.week_start() {
+            diary.week_start =
+                Weekday::from_str(week_start).map_err(|_| DiaryError::BadWeekday {
+                    weekday: week_start.clone(),
+                })?;
+        }
+        diary.template = cfg.template().clone();
+        diary.book_title = cfg.book_title().clone();
+        diary.author = cfg.author().clone();
+        diary.front_matter = cfg.front_matter();
+        Ok(diary)
     }
 
     pub const fn prefix(&self) -> &String {
         &self.prefix
     }
+    pub const fn layout(&self) -> &Layout {
+        &self.layout
+    }
     pub const fn diary_path(&self) -> &PathBuf {
         &self.diary_path
     }
     pub const fn file_type(&self) -> &EntryFileType {
         &self.file_type
     }
+    pub const fn template(&self) -> &Option<String> {
+        &self.template
+    }
+    pub const fn locale(&self) -> Locale {
+        self.locale
+    }
+    pub const fn ordinal_style(&self) -> OrdinalStyle {
+        self.ordinal_style
+    }
+    /// The first day of the week in calendars.
+    pub const fn week_start(&self) -> Weekday {
+        self.week_start
+    }
+    pub const fn book_title(&self) -> &Option<String> {
+        &self.book_title
+    }
+    pub const fn author(&self) -> &Option<String> {
+        &self.author
+    }
+    pub const fn front_matter(&self) -> bool {
+        self.front_matter
+    }
+    /// The value of a date placeholder, as used in the title and tag formats.
+    fn date_value(&self, placeholder: &str, date: &DateTime<Local>) -> String {
+        match placeholder {
+            "weekday" => self.locale.weekday(date).to_owned(),
+            "day" => date.day().to_string(),
+            "ordinal" => {
+                let ordinals = self.locale.ordinals();
+                let suffix = ordinals.suffix(date.day());
+                match self.ordinal_style {
+                    OrdinalStyle::None => String::new(),
+                    OrdinalStyle::Superscript if !suffix.is_empty() && ordinals.raised() => {
+                        self.file_type.superscript(suffix)
+                    }
+                    _ => suffix.to_owned(),
+                }
+            }
+            "month" => self.locale.month(date).to_owned(),
+            "year" => date.year().to_string(),
+            "iso_date" => date.format("%Y-%m-%d").to_string(),
+            "week" => date.iso_week().week().to_string(),
+            _ => String::new(),
+        }
+    }
+    /// The folder holding the entry templates.
+    pub fn templates_path(&self) -> PathBuf {
+        self.diary_path.join("templates")
+    }
+    /// Expands the date placeholders in an entry template, where `{{` and `}}` are
+    /// literal braces.
+    pub fn render_template(
+        &self,
+        template: &str,
+        date: &DateTime<Local>,
+    ) -> Result<String, DiaryError> {
+        let format = FormatString::parse(template, &DATE_PLACEHOLDERS)?;
+        Ok(format.render(|name| self.date_value(name, date)))
+    }
+    /// The title of the entry for the given date, using the title format if one is configured.
+    pub fn title(&self, date: &DateTime<Local>) -> String {
+        self.title_format.as_ref().map_or_else(
+            || self.file_type.title(date, self.ordinal_style),
+            |format| {
+                let title = format.render(|name| self.date_value(name, date));
+                self.file_type.heading(&title, 1)
+            },
+        )
+    }
+    /// The text of a tag, using the tag format if one is configured.
+    fn tag_text(&self, tag_name: String, date: &DateTime<Local>) -> String {
+        match &self.tag_format {
+            Some(format) => format.render(|name| match name {
+                "tag" => tag_name.clone(),
+                _ => self.date_value(name, date),
+            }),
+            None => tag_name,
+        }
+    }
+    /// The inverse of `tag_text`, reading the tag back from the text of a tag heading.
+    /// Text that doesn't follow the tag format, e.g. a heading written by hand, is kept
+    /// as is.
+    pub fn tag_name(&self, text: &str, date: &DateTime<Local>) -> String {
+        self.tag_format
+            .as_ref()
+            .and_then(|format| format.read(text, "tag", |name| self.date_value(name, date)))
+            .unwrap_or_else(|| text.to_owned())
+    }
+    /// A tag heading, using the tag format if one is configured.
+    pub fn tag(&self, tag_name: String, date: &DateTime<Local>) -> String {
+        self.file_type.tag(self.tag_text(tag_name, date))
+    }
+    /// A timestamp heading, with the optional tag using the tag format if one is configured.
+    pub fn timestamp(
+        &self,
+        time: &NaiveTime,
+        tag_name: Option<String>,
+        date: &DateTime<Local>,
+    ) -> String {
+        let tag_text = tag_name.map(|tag_name| self.tag_text(tag_name, date));
+        self.file_type.timestamp(time, tag_text)
+    }
     pub fn file_name(&self, date: &DateTime<Local>) -> PathBuf {
-        let entry_suffix = date.format("%Y-%m-%d").to_string();
-        let file_name = format!(
-            "{}_{}.{}",
-            self.prefix,
-            entry_suffix,
-            self.file_type.extension()
-        );
-        PathBuf::from(file_name)
+        let entry_path = self.entry_path(date);
+        entry_path
+            .file_name()
+            .map_or_else(|| entry_path.clone(), PathBuf::from)
+    }
+    /// The path of the entry for the given date, relative to the diary folder.
+    fn entry_path(&self, date: &DateTime<Local>) -> PathBuf {
+        self.layout
+            .render(date, &self.prefix, self.file_type.extension())
     }
     pub fn get_entry_path(&self, date: &DateTime<Local>) -> PathBuf {
-        let mut entry_path = file_system::month_folder(self.diary_path(), date);
-        let entry_name = self.file_name(date);
-        entry_path.push(entry_name);
-        entry_path
+        self.diary_path.join(self.entry_path(date))
     }
-    pub fn get_entry_file(&self, date: &DateTime<Local>) -> io::Result<File> {
+    /// The path of a named entry for the given date, e.g. `diary_2022-01-13_evening.md`,
+    /// or of the main entry when no name is given.
+    ///
+    /// # Returns
+    ///
+    /// The path, or a DiaryError if the name has characters other than letters,
+    /// numbers and dashes.
+    pub fn get_named_entry_path(
+        &self,
+        date: &DateTime<Local>,
+        name: Option<&str>,
+    ) -> Result<PathBuf, DiaryError> {
         let entry_path = self.get_entry_path(date);
-        return OpenOptions::new().append(true).open(entry_path);
+        match name {
+            Some(name) if !is_entry_name(name) => Err(DiaryError::BadEntryName {
+                name: name.to_owned(),
+            }),
+            Some(name) => {
+                let stem = entry_path.file_stem().unwrap_or_default().to_string_lossy();
+                let file_name = format!("{}_{}.{}", stem, name, self.file_type.extension());
+                Ok(entry_path.with_file_name(file_name))
+            }
+            None => Ok(entry_path),
+        }
+    }
+    pub fn get_entry_file(&self, date: &DateTime<Local>) -> io::Result<File> {
+        OpenOptions::new()
+            .append(true)
+            .open(self.get_entry_path(date))
+    }
+    /// Opens a named entry, or the main entry when no name is given, for appending.
+    ///
+    /// # Returns
+    ///
+    /// The file, or a DiaryError if the name is invalid, the entry doesn't exist or
+    /// on any other IO issues.
+    pub fn get_named_entry_file(
+        &self,
+        date: &DateTime<Local>,
+        name: Option<&str>,
+    ) -> Result<File, DiaryError> {
+        let entry_path = self.get_named_entry_path(date, name)?;
+        match OpenOptions::new().append(true).open(entry_path) {
+            Ok(file) => Ok(file),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                Err(DiaryError::NoEntry { source: Some(e) })
+            }
+            Err(e) => Err(e.into()), // uncovered.
+        }
+    }
+    /// The date and name of the entry at the given path, if it is an entry of this diary.
+    fn entry_date(&self, path: &Path) -> Option<(DateTime<Local>, Option<String>)> {
+        let relative_path = path.strip_prefix(&self.diary_path).ok()?;
+        let extension = self.file_type.extension();
+        if let Some(date) = self.layout.read(relative_path, &self.prefix, extension) {
+            return Some((date::local_date(date), None));
+        }
+
+        let stem = relative_path.file_stem()?.to_str()?;
+        let (main_stem, name) = stem.rsplit_once('_')?;
+        let main_path = relative_path.with_file_name(format!("{}.{}", main_stem, extension));
+        let date = self.layout.read(&main_path, &self.prefix, extension)?;
+
+        is_entry_name(name).then(|| (date::local_date(date), Some(name.to_owned())))
+    }
+    /// Reads the content of an entry into an Entry, with the tags of its sections read
+    /// back through the tag format.
+    pub fn parse_entry(&self, date: &DateTime<Local>, content: &str) -> Entry {
+        let mut entry = self.file_type.parse(date, content);
+        for section in &mut entry.sections {
+            section.tag = self.tag_name(&section.tag, date);
+        }
+        entry
+    }
+    /// Reads an entry file into an Entry.
+    pub fn read_entry(&self, entry_file: &EntryFile) -> Result<Entry, DiaryError> {
+        let content = std::fs::read_to_string(&entry_file.path)?;
+        Ok(self.parse_entry(&entry_file.date, &content))
+    }
+    /// Finds every entry in the diary folder, sorted by date with the main entry of
+    /// each day first.
+    pub fn entries(&self) -> Result<Vec<EntryFile>, DiaryError> {
+        let mut entries: Vec<EntryFile> = file_system::walk_files(self.diary_path())?
+            .into_iter()
+            .filter_map(|path| {
+                self.entry_date(&path)
+                    .map(|(date, name)| EntryFile { date, name, path })
+            })
+            .collect();
+        entries.sort_by(|a, b| (a.date, &a.name).cmp(&(b.date, &b.name)));
+        Ok(entries)
+    }
+    /// A copy of the diary that finds and writes entries using a different prefix and layout.
+    #[must_use]
+    pub fn with_layout(&self, prefix: &str, layout: Layout) -> Self {
+        Self {
+            prefix: prefix.to_owned(),
+            layout,
+            ..self.clone()
+        }
+    }
+    /// A copy of the diary that writes entries using a different file type.
+    #[must_use]
+    pub fn with_file_type(&self, file_type: EntryFileType) -> Self {
+        Self {
+            file_type,
+            ..self.clone()
+        }
     }
 }
 
@@ -185,8 +889,11 @@ mod tests {
 
     use chrono::prelude::*;
 
-    use super::{process_file_type, Diary, EntryContent, EntryFileType, MarkdownDiary, RstDiary};
-    use crate::config::Config;
+    use super::{
+        process_file_type, AsciiDocDiary, Diary, EntryContent, EntryFileType, MarkdownDiary,
+        OrgDiary, PlainTextDiary, RstDiary,
+    };
+    use crate::{config::Config, ops::testing, utils::date::OrdinalStyle};
 
     #[test]
     fn get_extension() {
@@ -196,7 +903,19 @@ mod tests {
 
         let entry_file = EntryFileType::from_str("md").unwrap();
 
-        assert_eq!(entry_file.extension(), "md")
+        assert_eq!(entry_file.extension(), "md");
+
+        let entry_file = EntryFileType::from_str("org").unwrap();
+
+        assert_eq!(entry_file.extension(), "org");
+
+        let entry_file = EntryFileType::from_str("adoc").unwrap();
+
+        assert_eq!(entry_file.extension(), "adoc");
+
+        let entry_file = EntryFileType::from_str("txt").unwrap();
+
+        assert_eq!(entry_file.extension(), "txt")
     }
 
     #[test]
@@ -210,7 +929,7 @@ mod tests {
         let entry_file = RstDiary {};
         let entry_date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
 
-        let actual_header = entry_file.title(&entry_date);
+        let actual_header = entry_file.title(&entry_date, OrdinalStyle::Superscript);
 
         let expected_header =
             "Saturday 6\\ :sup:`th` November 2021\n===================================\n\n";
@@ -234,7 +953,7 @@ mod tests {
         let entry_file = MarkdownDiary {};
         let entry_date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
 
-        let actual_header = entry_file.title(&entry_date);
+        let actual_header = entry_file.title(&entry_date, OrdinalStyle::Superscript);
 
         let expected_header = "# Saturday 6<sup>th</sup> November 2021\n\n";
 
@@ -252,6 +971,199 @@ mod tests {
         assert_eq!(actual_tag, expected_tag)
     }
 
+    #[test]
+    fn org_title() {
+        let entry_file = OrgDiary {};
+        let entry_date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+
+        let actual_header = entry_file.title(&entry_date, OrdinalStyle::Superscript);
+
+        let expected_header = "* Saturday 6^{th} November 2021\n\n";
+
+        assert_eq!(actual_header, expected_header)
+    }
+
+    #[test]
+    fn org_tag() {
+        let entry_file = OrgDiary {};
+
+        let actual_tag = entry_file.tag("Meeting".to_string());
+
+        let expected_tag = "** Meeting\n\n";
+
+        assert_eq!(actual_tag, expected_tag)
+    }
+
+    #[test]
+    fn adoc_title() {
+        let entry_file = AsciiDocDiary {};
+        let entry_date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+
+        let actual_header = entry_file.title(&entry_date, OrdinalStyle::Superscript);
+
+        let expected_header = "= Saturday 6^th^ November 2021\n\n";
+
+        assert_eq!(actual_header, expected_header)
+    }
+
+    #[test]
+    fn adoc_tag() {
+        let entry_file = AsciiDocDiary {};
+
+        let actual_tag = entry_file.tag("Meeting".to_string());
+
+        let expected_tag = "== Meeting\n\n";
+
+        assert_eq!(actual_tag, expected_tag)
+    }
+
+    #[test]
+    fn txt_title() {
+        let entry_file = PlainTextDiary {};
+        let entry_date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+
+        let actual_header = entry_file.title(&entry_date, OrdinalStyle::Superscript);
+
+        let expected_header = "Saturday 6th November 2021\n==========================\n\n";
+
+        assert_eq!(actual_header, expected_header)
+    }
+
+    #[test]
+    fn txt_tag() {
+        let entry_file = PlainTextDiary {};
+
+        let actual_tag = entry_file.tag("Meeting".to_string());
+
+        let expected_tag = "[Meeting]\n\n";
+
+        assert_eq!(actual_tag, expected_tag)
+    }
+
+    #[test]
+    fn read_heading_inverts_heading() {
+        for file_type in ["md", "rst", "org", "adoc", "txt"] {
+            let entry_file = EntryFileType::from_str(file_type).unwrap();
+
+            for level in 1..=3 {
+                let heading = entry_file.heading("A heading", level);
+                let lines: Vec<&str> = heading.lines().collect();
+
+                let (text, read_level, span) = entry_file.read_heading(&lines).unwrap();
+
+                assert_eq!(text, "A heading");
+                assert_eq!(read_level, level);
+                assert_eq!(span, lines.len() - 1);
+            }
+        }
+    }
+
+    #[test]
+    fn md_timestamp() {
+        let entry_file = MarkdownDiary {};
+        let time = NaiveTime::from_hms_opt(14, 32, 10).unwrap();
+
+        assert_eq!(
+            entry_file.timestamp(&time, Some("Meeting".to_string())),
+            "### 14:32 — Meeting\n\n"
+        );
+        assert_eq!(entry_file.timestamp(&time, None), "### 14:32\n\n");
+    }
+
+    #[test]
+    fn rst_timestamp() {
+        let entry_file = RstDiary {};
+        let time = NaiveTime::from_hms_opt(9, 5, 0).unwrap();
+
+        assert_eq!(
+            entry_file.timestamp(&time, Some("Meeting".to_string())),
+            "09:05 — Meeting\n~~~~~~~~~~~~~~~\n\n"
+        );
+    }
+
+    #[test]
+    fn read_heading_ignores_text() {
+        for file_type in ["md", "rst", "org", "adoc", "txt"] {
+            let entry_file = EntryFileType::from_str(file_type).unwrap();
+
+            assert!(entry_file
+                .read_heading(&["Some text", "more text", ""])
+                .is_none());
+        }
+    }
+
+    #[test]
+    fn diary_read_entry() {
+        let config = testing::temp_config();
+        testing::default_init(config.diary_path());
+        let entry_date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+        testing::new_entry(&config, &entry_date);
+        let diary = Diary::from_config(&config).unwrap();
+
+        let entries = diary.entries().unwrap();
+        let entry = diary.read_entry(&entries[0]).unwrap();
+
+        assert_eq!(entry.date, entry_date);
+        assert_eq!(entry.title, "Saturday 6<sup>th</sup> November 2021");
+        assert!(entry.sections.is_empty());
+    }
+
+    #[test]
+    fn diary_named_entries() {
+        let config = testing::temp_config();
+        testing::default_init(config.diary_path());
+        let entry_date = Local.with_ymd_and_hms(2022, 1, 13, 0, 0, 0).unwrap();
+        testing::new_entry(&config, &entry_date);
+        let diary = Diary::from_config(&config).unwrap();
+        let evening_path = diary
+            .get_named_entry_path(&entry_date, Some("evening"))
+            .unwrap();
+        std::fs::write(&evening_path, "").unwrap();
+        std::fs::write(evening_path.with_file_name("diary_2022-01-13_a_b.md"), "").unwrap();
+
+        let entries = diary.entries().unwrap();
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].name, None);
+        assert_eq!(entries[1].name.as_deref(), Some("evening"));
+        assert_eq!(entries[1].date, entry_date);
+    }
+
+    #[test]
+    fn diary_layout() {
+        let config = Config::builder()
+            .diary_path(testing::temp_diary_path())
+            .layout("{year}/{month}/{prefix}_{date}.{ext}")
+            .build();
+        testing::default_init(config.diary_path());
+        let entry_date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+        testing::new_entry(&config, &entry_date);
+        let diary = Diary::from_config(&config).unwrap();
+
+        let entry_path = diary.get_entry_path(&entry_date);
+        let expected_path: PathBuf = [
+            config.diary_path(),
+            &PathBuf::from("2021/11/diary_2021-11-06.md"),
+        ]
+        .iter()
+        .collect();
+
+        assert_eq!(entry_path, expected_path);
+        assert!(entry_path.exists());
+        assert_eq!(diary.entries().unwrap()[0].path, entry_path);
+    }
+
+    #[test]
+    #[should_panic(expected = "value: BadFormat")]
+    fn diary_ambiguous_layout() {
+        let cfg = Config::builder()
+            .diary_path("/".into())
+            .layout("{year}/{month}.{ext}")
+            .build();
+
+        Diary::from_config(&cfg).unwrap();
+    }
+
     #[test]
     fn diary_file_from_config() {
         let cfg = Config::builder()
@@ -265,4 +1177,187 @@ mod tests {
         assert_eq!(diary_file.diary_path(), &PathBuf::from("/"));
         assert_eq!(diary_file.prefix(), "diary")
     }
+
+    #[test]
+    fn diary_title_format() {
+        let cfg = Config::builder()
+            .diary_path("/".into())
+            .title_format("{iso_date} ({weekday}, week {week})")
+            .build();
+        let entry_date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+
+        let diary = Diary::from_config(&cfg).unwrap();
+
+        assert_eq!(
+            diary.title(&entry_date),
+            "# 2021-11-06 (Saturday, week 44)\n\n"
+        )
+    }
+
+    #[test]
+    fn diary_default_title_format_matches_built_in() {
+        let entry_date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+
+        for (name, style) in [
+            ("superscript", OrdinalStyle::Superscript),
+            ("inline", OrdinalStyle::Inline),
+            ("none", OrdinalStyle::None),
+        ] {
+            let cfg = Config::builder()
+                .file_type("rst")
+                .diary_path("/".into())
+                .title_format("{weekday} {day}{ordinal} {month} {year}")
+                .ordinal_style(name)
+                .build();
+
+            let diary = Diary::from_config(&cfg).unwrap();
+
+            assert_eq!(
+                diary.title(&entry_date),
+                RstDiary {}.title(&entry_date, style)
+            )
+        }
+    }
+
+    #[test]
+    fn diary_tag_format() {
+        let cfg = Config::builder()
+            .diary_path("/".into())
+            .tag_format("{tag} ({day} {month})")
+            .build();
+        let entry_date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+
+        let diary = Diary::from_config(&cfg).unwrap();
+
+        assert_eq!(
+            diary.tag("Meeting".to_string(), &entry_date),
+            "## Meeting (6 November)\n\n"
+        );
+        assert_eq!(
+            diary.tag_name("Meeting (6 November)", &entry_date),
+            "Meeting"
+        );
+        assert_eq!(diary.tag_name("Notes", &entry_date), "Notes");
+    }
+
+    #[test]
+    fn diary_localised_titles() {
+        let first = Local.with_ymd_and_hms(2021, 11, 1, 0, 0, 0).unwrap();
+        let sixth = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+
+        let cases = [
+            ("fr", &first, "# lundi 1<sup>er</sup> novembre 2021\n\n"),
+            ("fr", &sixth, "# samedi 6 novembre 2021\n\n"),
+            ("de", &sixth, "# Samstag, 6. November 2021\n\n"),
+            ("es", &sixth, "# sábado 6 de noviembre de 2021\n\n"),
+            ("nl", &sixth, "# zaterdag 6 november 2021\n\n"),
+            ("en", &sixth, "# Saturday 6<sup>th</sup> November 2021\n\n"),
+        ];
+
+        for (locale, date, expected) in cases {
+            let cfg = Config::builder()
+                .diary_path("/".into())
+                .locale(locale)
+                .build();
+            let diary = Diary::from_config(&cfg).unwrap();
+
+            assert_eq!(diary.title(date), expected);
+        }
+    }
+
+    #[test]
+    fn diary_week_start() {
+        let cfg = Config::builder()
+            .diary_path("/".into())
+            .week_start("sunday")
+            .build();
+        let diary = Diary::from_config(&cfg).unwrap();
+
+        assert_eq!(diary.week_start(), Weekday::Sun);
+    }
+
+    #[test]
+    #[should_panic(expected = "value: BadWeekday")]
+    fn diary_bad_week_start() {
+        let cfg = Config::builder()
+            .diary_path("/".into())
+            .week_start("someday")
+            .build();
+        Diary::from_config(&cfg).unwrap();
+    }
+
+    #[test]
+    fn diary_ordinal_styles() {
+        let entry_date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+
+        let cases = [
+            ("md", "inline", "# Saturday 6th November 2021\n\n"),
+            ("md", "none", "# Saturday 6 November 2021\n\n"),
+            (
+                "rst",
+                "none",
+                "Saturday 6 November 2021\n========================\n\n",
+            ),
+            (
+                "rst",
+                "superscript",
+                "Saturday 6\\ :sup:`th` November 2021\n===================================\n\n",
+            ),
+        ];
+
+        for (file_type, style, expected) in cases {
+            let cfg = Config::builder()
+                .diary_path("/".into())
+                .file_type(file_type)
+                .ordinal_style(style)
+                .build();
+            let diary = Diary::from_config(&cfg).unwrap();
+
+            assert_eq!(diary.title(&entry_date), expected);
+        }
+    }
+
+    #[test]
+    fn md_title_teens() {
+        let entry_file = MarkdownDiary {};
+        let entry_date = Local.with_ymd_and_hms(2021, 11, 12, 0, 0, 0).unwrap();
+
+        let actual_header = entry_file.title(&entry_date, OrdinalStyle::Superscript);
+
+        let expected_header = "# Friday 12<sup>th</sup> November 2021\n\n";
+
+        assert_eq!(actual_header, expected_header)
+    }
+
+    #[test]
+    fn strip_superscript() {
+        let file_types: [(EntryFileType, &str); 5] = [
+            (
+                MarkdownDiary {}.into(),
+                "Saturday 6<sup>th</sup> November 2021",
+            ),
+            (RstDiary {}.into(), "Saturday 6\\ :sup:`th` November 2021"),
+            (OrgDiary {}.into(), "Saturday 6^{th} November 2021"),
+            (AsciiDocDiary {}.into(), "Saturday 6^th^ November 2021"),
+            (PlainTextDiary {}.into(), "Saturday 6th November 2021"),
+        ];
+
+        for (file_type, title) in file_types {
+            assert_eq!(
+                file_type.strip_superscript(title),
+                "Saturday 6th November 2021"
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "value: BadFormat")]
+    fn diary_title_format_unknown_placeholder() {
+        let cfg = Config::builder()
+            .diary_path("/".into())
+            .title_format("{tag}")
+            .build();
+
+        Diary::from_config(&cfg).unwrap();
+    }
 }
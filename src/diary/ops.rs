@@ -1,9 +1,19 @@
 pub mod init;
 pub use self::init::{init, InitOptions};
 pub mod add;
+pub mod cal;
 pub mod commit;
+pub mod convert;
+pub mod export;
+pub mod import;
+pub mod list;
+pub mod migrate;
 pub mod new;
 pub mod open;
+pub mod search;
+pub mod show;
+pub mod stats;
+pub mod tags;
 
 #[cfg(test)]
 pub mod testing {
@@ -15,7 +25,10 @@ pub mod testing {
     use super::{init, InitOptions};
     use crate::{
         config::Config,
-        ops::new::{new, NewOptions},
+        ops::{
+            add::{add, AddOptions},
+            new::{new, NewOptions},
+        },
         utils::editing::test::test_string_getter,
         Diary,
     };
@@ -35,11 +48,45 @@ pub mod testing {
     }
 
     pub fn new_entry(config: &Config, entry_date: &DateTime<Local>) {
-        let new_opts = NewOptions { open: false };
+        let new_opts = NewOptions {
+            open: false,
+            template: None,
+            name: None,
+        };
         let diary = Diary::from_config(config).unwrap();
         new(&new_opts, &diary, entry_date, test_string_getter).unwrap();
     }
 
+    pub fn add_tagged(config: &Config, date: &DateTime<Local>, tag: &str, content: &str) {
+        let diary = Diary::from_config(config).unwrap();
+        let add_opts = AddOptions {
+            tag: Some(tag.to_owned()),
+            content: Some(content.to_owned()),
+            time: None,
+            name: None,
+        };
+        add(&add_opts, &diary, date, test_string_getter).unwrap();
+    }
+
+    /// Adds the given tagged content to a diary, creating the entry of each date.
+    pub fn add_entries(config: &Config, entries: &[(DateTime<Local>, &str, &str)]) {
+        let diary = Diary::from_config(config).unwrap();
+        for (date, tag, content) in entries {
+            if !diary.get_entry_path(date).exists() {
+                new_entry(config, date);
+            }
+            add_tagged(config, date, tag, content);
+        }
+    }
+
+    /// A new diary with the given tagged content, creating the entry of each date.
+    pub fn diary_with_entries(entries: &[(DateTime<Local>, &str, &str)]) -> Config {
+        let config = temp_config();
+        default_init(config.diary_path());
+        add_entries(&config, entries);
+        config
+    }
+
     pub fn default_init(potential_path: &Path) {
         let init_opts = InitOptions {
             path: temp_path(),
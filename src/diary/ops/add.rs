@@ -2,7 +2,11 @@
 //!
 //! The add module contains functionality relating to the add command,
 //! independent of the CLI.
-use std::{fs::File, io::Write};
+use std::{
+    fs::{self, File},
+    io::Write,
+    path::Path,
+};
 
 use chrono::prelude::*;
 
@@ -14,6 +18,10 @@ pub struct AddOptions {
     pub tag: Option<String>,
     /// Optional adding contents.
     pub content: Option<String>,
+    /// The optional time to stamp the content with.
+    pub time: Option<NaiveTime>,
+    /// The name of the entry to add to, or None for the main entry.
+    pub name: Option<String>,
 }
 
 /// Adds the given content to a file.
@@ -22,23 +30,50 @@ pub struct AddOptions {
 ///
 /// * `file` The file to add the content to.
 /// * `content` The content to add to the file above.
-/// * `tag` The optional tag to place above the content.
+/// * `heading` The optional tag or timestamp heading to place above the content.
 ///
 /// # Errors
 ///
 /// * If the content provided is empty.
-fn add_content(mut file: File, content: String, tag: Option<String>) -> Result<(), DiaryError> {
+fn add_content(mut file: File, content: String, heading: Option<String>) -> Result<(), DiaryError> {
     if content.is_empty() {
         return Err(DiaryError::NoContent);
     }
 
-    if let Some(tag) = tag {
-        file.write_all(tag.as_bytes())?;
+    if let Some(heading) = heading {
+        file.write_all(heading.as_bytes())?;
     }
     editing::add_user_content_to_file(&mut file, content)?;
     Ok(())
 }
 
+/// Lists a tag in the front matter of an entry, if the entry has front matter.
+///
+/// # Arguments
+///
+/// * `diary` - Struct representing the diary.
+/// * `entry_path` - The path of the entry.
+/// * `tag` - The tag to list.
+pub(crate) fn tag_front_matter(
+    diary: &Diary,
+    entry_path: &Path,
+    tag: &str,
+) -> Result<(), DiaryError> {
+    let content = fs::read_to_string(entry_path)?;
+    let lines: Vec<&str> = content.lines().collect();
+
+    if let Some((mut front_matter, span)) = diary.file_type().read_front_matter(&lines) {
+        if front_matter.add_tag(tag) {
+            let body: String = content.split_inclusive('\n').skip(span).collect();
+            fs::write(
+                entry_path,
+                diary.file_type().front_matter(&front_matter) + &body,
+            )?;
+        }
+    }
+    Ok(())
+}
+
 /// Adds user provided content to a diary entry.
 ///
 /// # Arguments
@@ -57,36 +92,39 @@ pub fn add(
     date: &DateTime<Local>,
     string_getter: editing::StringGetter,
 ) -> Result<(), DiaryError> {
-    let file = match diary.get_entry_file(date) {
-        Ok(file) => file,
-        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
-            return Err(DiaryError::NoEntry { source: Some(e) })
-        }
-        Err(e) => return Err(e.into()), // uncovered.
-    };
+    let file = diary.get_named_entry_file(date, opts.name.as_deref())?;
 
     let content = match &opts.content {
         Some(content) => content.to_owned() + "\n",
         None => string_getter("".to_owned())?,
     };
 
-    let tag_result = opts
-        .tag
-        .as_ref()
-        .map(|tag| diary.file_type().tag(tag.to_string()));
+    let heading = match (&opts.time, &opts.tag) {
+        (Some(time), tag) => Some(diary.timestamp(time, tag.clone(), date)),
+        (None, Some(tag)) => Some(diary.tag(tag.to_string(), date)),
+        (None, None) => None,
+    };
 
-    add_content(file, content, tag_result)
+    if let Some(tag) = &opts.tag {
+        if !content.is_empty() {
+            let entry_path = diary.get_named_entry_path(date, opts.name.as_deref())?;
+            tag_front_matter(diary, &entry_path, tag)?;
+        }
+    }
+    add_content(file, content, heading)
 }
 
 #[cfg(test)]
 mod test {
     use std::fs;
 
-    use chrono::{Local, TimeZone};
+    use chrono::{Local, NaiveTime, TimeZone};
 
     use crate::{
+        config::Config,
         ops::{
             add::{add, AddOptions},
+            new::{new, NewOptions},
             testing,
         },
         utils::editing::test::{test_empty_string_getter, test_string_getter},
@@ -107,6 +145,8 @@ mod test {
         let opts = AddOptions {
             tag: None,
             content: Some("testing quick add".to_owned()),
+            time: None,
+            name: None,
         };
         add(&opts, &diary, &entry_date, test_string_getter).unwrap();
 
@@ -133,6 +173,8 @@ mod test {
         let opts = AddOptions {
             tag: None,
             content: None,
+            time: None,
+            name: None,
         };
         add(&opts, &diary, &entry_date, test_string_getter).unwrap();
 
@@ -158,6 +200,8 @@ mod test {
         let opts = AddOptions {
             tag: Some("Tag".to_owned()),
             content: None,
+            time: None,
+            name: None,
         };
         add(&opts, &diary, &entry_date, test_string_getter).unwrap();
 
@@ -169,6 +213,124 @@ mod test {
         assert!(content.contains("Tag"));
     }
 
+    #[test]
+    fn add_with_time() {
+        let config = testing::temp_config();
+        testing::default_init(config.diary_path());
+
+        let entry_date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+        testing::new_entry(&config, &entry_date);
+
+        let diary = Diary::from_config(&config).unwrap();
+        let opts = AddOptions {
+            tag: Some("Meeting".to_owned()),
+            content: None,
+            time: NaiveTime::from_hms_opt(14, 32, 0),
+            name: None,
+        };
+        add(&opts, &diary, &entry_date, test_string_getter).unwrap();
+        let opts = AddOptions {
+            tag: None,
+            content: Some("Later on.".to_owned()),
+            time: NaiveTime::from_hms_opt(16, 5, 0),
+            name: None,
+        };
+        add(&opts, &diary, &entry_date, test_string_getter).unwrap();
+
+        let entry_path = diary.get_entry_path(&entry_date);
+
+        let content = fs::read_to_string(entry_path).unwrap();
+
+        assert!(content.contains("### 14:32 — Meeting\n\nTest content"));
+        assert!(content.contains("### 16:05\n\nLater on.\n"));
+    }
+
+    #[test]
+    fn add_to_named_entry() {
+        let config = testing::temp_config();
+        testing::default_init(config.diary_path());
+
+        let entry_date = Local.with_ymd_and_hms(2022, 1, 13, 0, 0, 0).unwrap();
+        testing::new_entry(&config, &entry_date);
+
+        let diary = Diary::from_config(&config).unwrap();
+        let opts = AddOptions {
+            tag: None,
+            content: Some("Evening thoughts.".to_owned()),
+            time: None,
+            name: Some("evening".to_owned()),
+        };
+        assert!(add(&opts, &diary, &entry_date, test_string_getter).is_err());
+
+        let new_opts = NewOptions {
+            open: false,
+            template: None,
+            name: Some("evening".to_owned()),
+        };
+        new(&new_opts, &diary, &entry_date, test_string_getter).unwrap();
+        add(&opts, &diary, &entry_date, test_string_getter).unwrap();
+
+        let main_content = fs::read_to_string(diary.get_entry_path(&entry_date)).unwrap();
+        let named_content = fs::read_to_string(
+            diary
+                .get_named_entry_path(&entry_date, Some("evening"))
+                .unwrap(),
+        )
+        .unwrap();
+
+        assert!(!main_content.contains("Evening thoughts."));
+        assert!(named_content.contains("Evening thoughts."));
+    }
+
+    #[test]
+    #[should_panic(expected = "value: BadEntryName")]
+    fn add_to_entry_outside_diary() {
+        let config = testing::temp_config();
+        testing::default_init(config.diary_path());
+
+        let entry_date = Local.with_ymd_and_hms(2022, 1, 13, 0, 0, 0).unwrap();
+        testing::new_entry(&config, &entry_date);
+
+        let diary = Diary::from_config(&config).unwrap();
+        let opts = AddOptions {
+            tag: None,
+            content: Some("Escaped.".to_owned()),
+            time: None,
+            name: Some("../../escaped".to_owned()),
+        };
+        add(&opts, &diary, &entry_date, test_string_getter).unwrap();
+    }
+
+    #[test]
+    fn add_tag_to_front_matter() {
+        let config = Config::builder()
+            .diary_path(testing::temp_diary_path())
+            .front_matter(true)
+            .build();
+        testing::default_init(config.diary_path());
+
+        let entry_date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+        testing::new_entry(&config, &entry_date);
+
+        let diary = Diary::from_config(&config).unwrap();
+        for tag in ["Meeting", "Tip", "Meeting"] {
+            let opts = AddOptions {
+                tag: Some(tag.to_owned()),
+                content: Some("Notes.".to_owned()),
+                time: None,
+                name: None,
+            };
+            add(&opts, &diary, &entry_date, test_string_getter).unwrap();
+        }
+
+        let entry_path = diary.get_entry_path(&entry_date);
+
+        let content = fs::read_to_string(entry_path).unwrap();
+
+        assert!(content.starts_with("---\ndate: 2021-11-06\nweekday: Saturday\nwords: 0\ntags: [Meeting, Tip]\n---\n\n# Saturday"));
+        assert!(content.ends_with("## Meeting\n\nNotes.\n"));
+    }
+
     #[test]
     #[should_panic(expected = "value: NoContent")]
     fn add_empty_string() {
@@ -182,6 +344,8 @@ mod test {
         let opts = AddOptions {
             tag: Some("Tag".to_owned()),
             content: None,
+            time: None,
+            name: None,
         };
         add(&opts, &diary, &entry_date, test_empty_string_getter).unwrap();
     }
@@ -198,6 +362,8 @@ mod test {
         let opts = AddOptions {
             tag: Some("Tag".to_owned()),
             content: None,
+            time: None,
+            name: None,
         };
         add(&opts, &diary, &entry_date, test_string_getter).unwrap();
     }
@@ -0,0 +1,283 @@
+//! # Calendar operations
+//!
+//! The cal module contains functionality relating to the cal command,
+//! independent of the CLI.
+use std::collections::BTreeMap;
+
+use chrono::prelude::*;
+
+use crate::{errors::DiaryError, utils::date::DateRange, Diary};
+
+/// The width of a rendered month, seven days of three characters each.
+const MONTH_WIDTH: usize = 21;
+
+/// The 256-colour backgrounds of each heat level above 0, from fewest words to most.
+const HEAT_COLOURS: [u8; 4] = [22, 28, 34, 40];
+
+/// The markers following the day of each heat level when colour is off.
+const HEAT_MARKERS: [char; 5] = [' ', '.', '+', '*', '#'];
+
+/// The options available to the cal command.
+pub struct CalOptions {
+    /// The year to draw.
+    pub year: i32,
+    /// The month to draw, or None for the whole year.
+    pub month: Option<u32>,
+    /// Whether to highlight days with ANSI colours rather than markers.
+    pub colour: bool,
+}
+
+/// Sums the words written on each day within a range, across all entries of the day.
+///
+/// # Arguments
+///
+/// * `diary` - Struct representing the diary.
+/// * `range` - The dates to count.
+///
+/// # Returns
+///
+/// The words of each day with an entry, or a DiaryError on any IO issues.
+pub fn day_words(
+    diary: &Diary,
+    range: DateRange,
+) -> Result<BTreeMap<NaiveDate, usize>, DiaryError> {
+    let mut words = BTreeMap::new();
+    for entry_file in diary.entries()? {
+        if !range.contains(&entry_file.date) {
+            continue;
+        }
+        let entry = diary.read_entry(&entry_file)?;
+        *words.entry(entry.date.date_naive()).or_default() += entry.word_count();
+    }
+    Ok(words)
+}
+
+/// The heat level of a day, from 0 for no entry to 4 for the most words.
+///
+/// # Arguments
+///
+/// * `words` - The words written on the day, or None if it has no entry.
+/// * `max_words` - The most words written on any day being drawn.
+pub fn heat_level(words: Option<usize>, max_words: usize) -> usize {
+    match words {
+        None => 0,
+        Some(_) if max_words == 0 => 1,
+        Some(words) => 1 + (words * 4).saturating_sub(1) / max_words,
+    }
+    .min(HEAT_MARKERS.len() - 1)
+}
+
+/// Draws a day of the calendar, three characters wide.
+fn draw_day(day: u32, level: usize, colour: bool) -> String {
+    if colour && level > 0 {
+        format!("\x1b[48;5;{}m{:>2}\x1b[0m ", HEAT_COLOURS[level - 1], day)
+    } else if colour {
+        format!("{:>2} ", day)
+    } else {
+        format!("{:>2}{}", day, HEAT_MARKERS[level])
+    }
+}
+
+/// Draws a month of the calendar as lines of the same visible width: the title,
+/// the weekday names and six rows of weeks.
+///
+/// # Arguments
+///
+/// * `year` - The year of the month.
+/// * `month` - The month to draw, 1 to 12.
+/// * `week_start` - The first day of each row.
+/// * `words` - The words written on each day with an entry.
+/// * `max_words` - The most words written on any day being drawn.
+/// * `colour` - Whether to highlight days with ANSI colours rather than markers.
+fn draw_month(
+    year: i32,
+    month: u32,
+    week_start: Weekday,
+    words: &BTreeMap<NaiveDate, usize>,
+    max_words: usize,
+    colour: bool,
+) -> Vec<String> {
+    let Some(first) = NaiveDate::from_ymd_opt(year, month, 1) else {
+        return vec![];
+    };
+    let title = first.format("%B %Y").to_string();
+    let mut lines = vec![format!("{:^width$}", title, width = MONTH_WIDTH)];
+
+    let weekdays: String = (0..7)
+        .map(|offset| {
+            let weekday = week_start_offset(week_start, offset);
+            let name = NaiveDate::from_isoywd_opt(2021, 1, weekday)
+                .expect("Every weekday of the first week of 2021 exists.")
+                .format("%a")
+                .to_string();
+            format!("{:<3}", &name[..2])
+        })
+        .collect();
+    lines.push(weekdays);
+
+    let padding =
+        (7 + first.weekday().num_days_from_monday() - week_start.num_days_from_monday()) % 7;
+    let mut cells = vec!["   ".to_owned(); padding as usize];
+    let mut date = Some(first);
+    while let Some(day) = date.filter(|day| day.month() == month) {
+        let level = heat_level(words.get(&day).copied(), max_words);
+        cells.push(draw_day(day.day(), level, colour));
+        date = day.succ_opt();
+    }
+    cells.resize(cells.len().div_ceil(7) * 7, "   ".to_owned());
+    lines.extend(cells.chunks(7).map(|week| week.concat()));
+    lines.resize(8, " ".repeat(MONTH_WIDTH));
+    lines
+}
+
+/// The day of the week the given number of days after the week start.
+fn week_start_offset(week_start: Weekday, offset: u32) -> Weekday {
+    (0..offset).fold(week_start, |weekday, _| weekday.succ())
+}
+
+/// Draws a calendar of a month or year with the days that have entries highlighted,
+/// more strongly the more words were written.
+///
+/// # Arguments
+///
+/// * `opts` - The options passed by the user at runtime.
+/// * `diary` - Struct representing the diary.
+///
+/// # Returns
+///
+/// The calendar, or a DiaryError if the month or year is out of range or on any
+/// IO issues.
+pub fn cal(opts: &CalOptions, diary: &Diary) -> Result<String, DiaryError> {
+    let range = opts
+        .month
+        .map_or_else(
+            || DateRange::year(opts.year),
+            |month| DateRange::month(opts.year, month),
+        )
+        .ok_or_else(|| DiaryError::BadDateRange {
+            reason: "the month or year is out of range".to_owned(),
+        })?;
+    let words = day_words(diary, range)?;
+    let max_words = words.values().copied().max().unwrap_or_default();
+    let draw = |month| {
+        draw_month(
+            opts.year,
+            month,
+            diary.week_start(),
+            &words,
+            max_words,
+            opts.colour,
+        )
+    };
+
+    let mut lines = vec![];
+    match opts.month {
+        Some(month) => lines.extend(draw(month)),
+        None => {
+            let width = MONTH_WIDTH * 3 + 4;
+            lines.push(format!("{:^width$}", opts.year, width = width));
+            lines.push(String::new());
+            for quarter in 0..4 {
+                let months: Vec<Vec<String>> = (1..=3).map(|m| draw(quarter * 3 + m)).collect();
+                for i in 0..months[0].len() {
+                    let row: Vec<&str> = months.iter().map(|month| month[i].as_str()).collect();
+                    lines.push(row.join("  "));
+                }
+            }
+        }
+    }
+
+    let calendar: Vec<&str> = lines.iter().map(|line| line.trim_end()).collect();
+    Ok(calendar.join("\n").trim_end().to_owned() + "\n")
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::prelude::*;
+
+    use super::{cal, heat_level, CalOptions};
+    use crate::{config::ConfigBuilder, ops::testing, Diary};
+
+    #[test]
+    fn heat_levels() {
+        assert_eq!(heat_level(None, 100), 0);
+        assert_eq!(heat_level(Some(0), 100), 1);
+        assert_eq!(heat_level(Some(25), 100), 1);
+        assert_eq!(heat_level(Some(26), 100), 2);
+        assert_eq!(heat_level(Some(100), 100), 4);
+        assert_eq!(heat_level(Some(0), 0), 1);
+    }
+
+    #[test]
+    fn draw_month() {
+        let config = testing::diary_with_entries(&[
+            (
+                Local.with_ymd_and_hms(2021, 11, 1, 0, 0, 0).unwrap(),
+                "work",
+                "One two three four.",
+            ),
+            (
+                Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap(),
+                "home",
+                "One.",
+            ),
+        ]);
+        let diary = Diary::from_config(&config).unwrap();
+
+        let opts = CalOptions {
+            year: 2021,
+            month: Some(11),
+            colour: false,
+        };
+        let calendar = cal(&opts, &diary).unwrap();
+
+        assert_eq!(
+            calendar,
+            "    November 2021\n\
+             Mo Tu We Th Fr Sa Su\n \
+             1# 2  3  4  5  6. 7\n \
+             8  9 10 11 12 13 14\n\
+             15 16 17 18 19 20 21\n\
+             22 23 24 25 26 27 28\n\
+             29 30\n"
+        );
+    }
+
+    #[test]
+    fn draw_month_sunday_start() {
+        let config = testing::temp_config();
+        testing::default_init(config.diary_path());
+        let config = ConfigBuilder::from(config).week_start("sun").build();
+        let diary = Diary::from_config(&config).unwrap();
+
+        let opts = CalOptions {
+            year: 2021,
+            month: Some(11),
+            colour: false,
+        };
+        let calendar = cal(&opts, &diary).unwrap();
+        let lines: Vec<&str> = calendar.lines().collect();
+
+        assert_eq!(lines[1], "Su Mo Tu We Th Fr Sa");
+        assert_eq!(lines[2], "    1  2  3  4  5  6");
+    }
+
+    #[test]
+    fn draw_year() {
+        let config = testing::temp_config();
+        testing::default_init(config.diary_path());
+        let diary = Diary::from_config(&config).unwrap();
+
+        let opts = CalOptions {
+            year: 2021,
+            month: None,
+            colour: true,
+        };
+        let calendar = cal(&opts, &diary).unwrap();
+
+        assert!(calendar.starts_with(&(" ".repeat(31) + "2021\n")));
+        assert!(calendar.contains("January 2021"));
+        assert!(calendar.contains("December 2021"));
+        assert!(!calendar.contains('\x1b'));
+    }
+}
@@ -8,10 +8,12 @@ pub struct CommitOptions {
     pub entry_date: DateTime<Local>,
     pub message: String,
     pub push: bool,
+    /// The name of the entry to commit, or None for the main entry.
+    pub name: Option<String>,
 }
 
 pub fn commit(opts: &CommitOptions, diary: &Diary) -> Result<(), DiaryError> {
-    let entry_path = diary.get_entry_path(&opts.entry_date);
+    let entry_path = diary.get_named_entry_path(&opts.entry_date, opts.name.as_deref())?;
     let relative_path = pathdiff::diff_paths(entry_path, diary.diary_path()).unwrap();
 
     let repo = Repository::open(diary.diary_path())?;
@@ -54,6 +56,7 @@ mod test {
             entry_date,
             message: "Test message".to_string(),
             push: false,
+            name: None,
         };
         let repo = Repository::open(config.diary_path()).unwrap();
 
@@ -86,6 +89,7 @@ mod test {
             entry_date,
             message: "Test message".to_string(),
             push: false,
+            name: None,
         };
         let repo = Repository::open(config.diary_path()).unwrap();
 
@@ -102,6 +106,7 @@ mod test {
             entry_date,
             message: "Test message".to_string(),
             push: false,
+            name: None,
         };
         let repo = Repository::open(config.diary_path()).unwrap();
 
@@ -132,6 +137,7 @@ mod test {
             entry_date,
             message: "Test message".to_string(),
             push: false,
+            name: None,
         };
 
         let diary = Diary::from_config(&config).unwrap();
@@ -158,6 +164,7 @@ mod test {
             entry_date,
             message: "Test message".to_string(),
             push: true,
+            name: None,
         };
 
         let diary = Diary::from_config(&config).unwrap();
@@ -0,0 +1,345 @@
+//! # Convert operations
+//!
+//! The convert module contains functionality relating to the convert command,
+//! independent of the CLI.
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+    path::PathBuf,
+    str::FromStr,
+};
+
+use chrono::prelude::*;
+
+use crate::{errors::DiaryError, read_timestamp, Diary, EntryContent, EntryFileType};
+
+/// The options available to the convert command.
+pub struct ConvertOptions {
+    /// The file type to convert the entries to.
+    pub to: String,
+    /// Whether to only report the changes rather than make them.
+    pub dry_run: bool,
+}
+
+/// An entry that has been, or would be, converted.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Conversion {
+    /// The path of the entry before conversion.
+    pub from: PathBuf,
+    /// The path of the entry after conversion.
+    pub to: PathBuf,
+}
+
+/// Re-writes the content of an entry using another file type.
+///
+/// Headings are read using the file type of `diary` and written using the file
+/// type of `target`, as is any front matter. The title, the first level-1 heading
+/// before any section, is written afresh for the entry date while the text between
+/// headings, and any later level-1 heading, is kept as is.
+///
+/// # Arguments
+///
+/// * `content` - The content of the entry.
+/// * `diary` - Struct representing the diary, with the current file type.
+/// * `target` - Struct representing the diary, with the target file type.
+/// * `date` - The date of the entry.
+pub fn convert_content(
+    content: &str,
+    diary: &Diary,
+    target: &Diary,
+    date: &DateTime<Local>,
+) -> String {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut converted = String::new();
+    let mut i = 0;
+    let mut titled = false;
+
+    if let Some((front_matter, span)) = diary.file_type().read_front_matter(&lines) {
+        converted.push_str(&target.file_type().front_matter(&front_matter));
+        i += span;
+    }
+
+    while i < lines.len() {
+        match diary.file_type().read_heading(&lines[i..]) {
+            Some((text, level, span)) => {
+                let section = level == 2 || (level == 3 && read_timestamp(&text).is_some());
+                let heading = match level {
+                    1 if !titled => target.title(date),
+                    _ => target.file_type().heading(&text, level),
+                };
+                titled |= level == 1 || section;
+                converted.push_str(&heading);
+                i += span;
+                // Headings are written with a trailing blank line already.
+                if lines.get(i).is_some_and(|line| line.trim().is_empty()) {
+                    i += 1;
+                }
+            }
+            None => {
+                converted.push_str(lines[i]);
+                converted.push('\n');
+                i += 1;
+            }
+        }
+    }
+    converted
+}
+
+/// Writes the converted content of every entry to its new path.
+///
+/// # Returns
+///
+/// DiaryError on any IO issues, after removing the files written so far.
+fn write_conversions(
+    conversions: &[(Conversion, DateTime<Local>)],
+    diary: &Diary,
+    target: &Diary,
+) -> Result<(), DiaryError> {
+    for (written, (conversion, date)) in conversions.iter().enumerate() {
+        let result = fs::read_to_string(&conversion.from).and_then(|content| {
+            let converted = convert_content(&content, diary, target, date);
+            let mut file = OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&conversion.to)?;
+            file.write_all(converted.as_bytes())
+        });
+        if let Err(e) = result {
+            for (conversion, _) in &conversions[..=written] {
+                // The failed file may not have been created.
+                let _ = fs::remove_file(&conversion.to);
+            }
+            return Err(e.into());
+        }
+    }
+    Ok(())
+}
+
+/// Converts every entry of a diary to another file type.
+///
+/// Every entry is written in the new file type before any of the old files are
+/// removed, so a failure part way through leaves the diary as it was.
+///
+/// # Arguments
+///
+/// * `opts` - The options passed by the user at runtime.
+/// * `diary` - Struct representing the diary.
+///
+/// # Returns
+///
+/// The conversions made, or that would be made on a dry run. DiaryError if the
+/// target file type is unsupported, if a converted entry would replace an existing
+/// file or on any IO issues.
+pub fn convert(opts: &ConvertOptions, diary: &Diary) -> Result<Vec<Conversion>, DiaryError> {
+    let target = diary.with_file_type(EntryFileType::from_str(&opts.to)?);
+    if target.file_type().extension() == diary.file_type().extension() {
+        return Ok(vec![]);
+    }
+
+    let mut conversions = vec![];
+    for entry in diary.entries()? {
+        let to = target.get_named_entry_path(&entry.date, entry.name.as_deref())?;
+        if to.exists() {
+            return Err(DiaryError::EntryClash { path: to });
+        }
+        conversions.push((
+            Conversion {
+                from: entry.path,
+                to,
+            },
+            entry.date,
+        ));
+    }
+    if !opts.dry_run {
+        write_conversions(&conversions, diary, &target)?;
+        for (conversion, _) in &conversions {
+            fs::remove_file(&conversion.from)?;
+        }
+    }
+    Ok(conversions
+        .into_iter()
+        .map(|(conversion, _)| conversion)
+        .collect())
+}
+
+#[cfg(test)]
+mod test {
+    use std::{fs, str::FromStr};
+
+    use chrono::prelude::*;
+
+    use super::{convert, convert_content, ConvertOptions};
+    use crate::{
+        errors::DiaryError,
+        ops::{
+            add::{add, AddOptions},
+            testing,
+        },
+        utils::editing::test::test_string_getter,
+        Diary, EntryFileType,
+    };
+
+    #[test]
+    fn convert_md_content_to_rst() {
+        let config = testing::temp_config();
+        testing::default_init(config.diary_path());
+        let diary = Diary::from_config(&config).unwrap();
+        let target = diary.with_file_type(EntryFileType::from_str("rst").unwrap());
+        let entry_date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+
+        let content =
+            "# Saturday 6<sup>th</sup> November 2021\n\nFirst thoughts.\n\n## Meeting\n\nNotes.\n";
+
+        let converted = convert_content(content, &diary, &target, &entry_date);
+
+        let expected = "Saturday 6\\ :sup:`th` November 2021\n===================================\n\nFirst thoughts.\n\nMeeting\n^^^^^^^\n\nNotes.\n";
+
+        assert_eq!(converted, expected)
+    }
+
+    #[test]
+    fn convert_rst_content_to_md() {
+        let config = testing::temp_config();
+        testing::default_init(config.diary_path());
+        let diary = Diary::from_config(&config).unwrap();
+        let source = diary.with_file_type(EntryFileType::from_str("rst").unwrap());
+        let entry_date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+
+        let content = "Saturday 6\\ :sup:`th` November 2021\n===================================\n\nMeeting\n^^^^^^^\n\nNotes.\n";
+
+        let converted = convert_content(content, &source, &diary, &entry_date);
+
+        let expected = "# Saturday 6<sup>th</sup> November 2021\n\n## Meeting\n\nNotes.\n";
+
+        assert_eq!(converted, expected)
+    }
+
+    #[test]
+    fn convert_keeps_later_level_one_headings() {
+        let config = testing::temp_config();
+        testing::default_init(config.diary_path());
+        let diary = Diary::from_config(&config).unwrap();
+        let target = diary.with_file_type(EntryFileType::from_str("rst").unwrap());
+        let entry_date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+
+        let content = "# Saturday 6<sup>th</sup> November 2021\n\n## Meeting\n\nNotes.\n\n# Summary\n\nA good day.\n";
+
+        let converted = convert_content(content, &diary, &target, &entry_date);
+
+        let expected = "Saturday 6\\ :sup:`th` November 2021\n===================================\n\nMeeting\n^^^^^^^\n\nNotes.\n\nSummary\n=======\n\nA good day.\n";
+
+        assert_eq!(converted, expected)
+    }
+
+    #[test]
+    fn convert_front_matter() {
+        let config = testing::temp_config();
+        testing::default_init(config.diary_path());
+        let diary = Diary::from_config(&config).unwrap();
+        let target = diary.with_file_type(EntryFileType::from_str("org").unwrap());
+        let entry_date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+
+        let content = "---\ndate: 2021-11-06\ntags: [Meeting]\n---\n\n# Saturday 6<sup>th</sup> November 2021\n\n";
+
+        let converted = convert_content(content, &diary, &target, &entry_date);
+
+        let expected = "#+date: 2021-11-06\n#+tags: Meeting\n\n* Saturday 6^{th} November 2021\n\n";
+
+        assert_eq!(converted, expected)
+    }
+
+    #[test]
+    fn convert_diary() {
+        let config = testing::temp_config();
+        testing::default_init(config.diary_path());
+        let diary = Diary::from_config(&config).unwrap();
+
+        let entry_date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+        testing::new_entry(&config, &entry_date);
+        let opts = AddOptions {
+            tag: Some("Meeting".to_owned()),
+            content: Some("Notes.".to_owned()),
+            time: None,
+            name: None,
+        };
+        add(&opts, &diary, &entry_date, test_string_getter).unwrap();
+
+        let opts = ConvertOptions {
+            to: "org".to_owned(),
+            dry_run: false,
+        };
+        let conversions = convert(&opts, &diary).unwrap();
+
+        assert_eq!(conversions.len(), 1);
+        assert!(!conversions[0].from.exists());
+
+        let content = fs::read_to_string(&conversions[0].to).unwrap();
+        assert_eq!(
+            content,
+            "* Saturday 6^{th} November 2021\n\n** Meeting\n\nNotes.\n"
+        );
+    }
+
+    #[test]
+    fn convert_diary_dry_run() {
+        let config = testing::temp_config();
+        testing::default_init(config.diary_path());
+        let diary = Diary::from_config(&config).unwrap();
+
+        let entry_date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+        testing::new_entry(&config, &entry_date);
+
+        let opts = ConvertOptions {
+            to: "rst".to_owned(),
+            dry_run: true,
+        };
+        let conversions = convert(&opts, &diary).unwrap();
+
+        assert_eq!(conversions.len(), 1);
+        assert!(conversions[0].from.exists());
+        assert!(!conversions[0].to.exists());
+        assert_eq!(conversions[0].to.extension().unwrap(), "rst");
+    }
+
+    #[test]
+    #[should_panic(expected = "value: BadFileType")]
+    fn convert_bad_file_type() {
+        let config = testing::temp_config();
+        testing::default_init(config.diary_path());
+        let diary = Diary::from_config(&config).unwrap();
+
+        let opts = ConvertOptions {
+            to: "docx".to_owned(),
+            dry_run: false,
+        };
+        convert(&opts, &diary).unwrap();
+    }
+
+    #[test]
+    fn convert_clash_changes_nothing() {
+        let config = testing::temp_config();
+        testing::default_init(config.diary_path());
+        let diary = Diary::from_config(&config).unwrap();
+
+        for day in [6, 7] {
+            let entry_date = Local.with_ymd_and_hms(2021, 11, day, 0, 0, 0).unwrap();
+            testing::new_entry(&config, &entry_date);
+        }
+        let target = diary.with_file_type(EntryFileType::from_str("rst").unwrap());
+        let clash_date = Local.with_ymd_and_hms(2021, 11, 7, 0, 0, 0).unwrap();
+        let clash = target.get_entry_path(&clash_date);
+        fs::write(&clash, "Already here.\n").unwrap();
+
+        let opts = ConvertOptions {
+            to: "rst".to_owned(),
+            dry_run: false,
+        };
+        let result = convert(&opts, &diary);
+
+        assert!(matches!(result, Err(DiaryError::EntryClash { path }) if path == clash));
+        assert_eq!(diary.entries().unwrap().len(), 2);
+        let converted_date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+        assert!(!target.get_entry_path(&converted_date).exists());
+        assert_eq!(fs::read_to_string(&clash).unwrap(), "Already here.\n");
+    }
+}
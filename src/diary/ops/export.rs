@@ -0,0 +1,10 @@
+//! # Export operations
+//!
+//! The export module contains functionality relating to the export command,
+//! independent of the CLI. The HTML site, single-document book and EPUB are exported
+//! by the html, book and epub modules, while JSON and CSV share the entry records of
+//! the records module.
+pub mod book;
+pub mod epub;
+pub mod html;
+pub mod records;
@@ -0,0 +1,310 @@
+//! # Book export
+//!
+//! Exports the entries within a range of dates as a single document of the diary's
+//! file type, with a table of contents and each entry's headings one level lower.
+use std::{collections::HashMap, fs};
+
+use crate::{errors::DiaryError, utils::date::DateRange, Diary, EntryContent, EntryFileType};
+
+/// The deepest heading level written, the most markdown supports.
+const MAX_LEVEL: usize = 6;
+
+/// The options available to the book export.
+pub struct BookOptions {
+    /// The dates of the entries to include.
+    pub range: DateRange,
+}
+
+/// The title of a book covering a range of dates, starting with the configured
+/// book title or `Diary`.
+pub(super) fn book_title(diary: &Diary, range: DateRange) -> String {
+    let title = diary.book_title().as_deref().unwrap_or("Diary");
+    match (range.from, range.to) {
+        (Some(from), Some(to)) => format!("{}, {} to {}", title, from, to),
+        (Some(from), None) => format!("{} from {}", title, from),
+        (None, Some(to)) => format!("{} to {}", title, to),
+        (None, None) => title.to_owned(),
+    }
+}
+
+/// Gives each markdown heading the anchor a renderer such as GitHub's would, numbering
+/// repeated headings in the order they appear.
+#[derive(Default)]
+pub(super) struct Anchors {
+    seen: HashMap<String, usize>,
+}
+
+impl Anchors {
+    pub(super) fn next(&mut self, text: &str) -> String {
+        let anchor: String = text
+            .trim()
+            .to_lowercase()
+            .chars()
+            .filter_map(|c| match c {
+                ' ' => Some('-'),
+                c if c.is_alphanumeric() || c == '-' || c == '_' => Some(c),
+                _ => None,
+            })
+            .collect();
+        let count = self.seen.entry(anchor.clone()).or_default();
+        *count += 1;
+        match *count {
+            1 => anchor,
+            count => format!("{}-{}", anchor, count - 1),
+        }
+    }
+}
+
+/// The level a heading moves to in the book, one below its level in the entry.
+///
+/// Plain text tags are bracketed rather than underlined, so they stay as they are
+/// while titles and other headings move to the next underlined level.
+fn demote(file_type: &EntryFileType, level: usize) -> usize {
+    let level = match (file_type, level) {
+        (EntryFileType::PlainTextDiary(_), 1) => 3,
+        (EntryFileType::PlainTextDiary(_), 2) => 2,
+        (_, level) => level + 1,
+    };
+    level.min(MAX_LEVEL)
+}
+
+/// An item of the table of contents, linking to an entry's heading where the file
+/// type supports it.
+///
+/// # Arguments
+///
+/// * `file_type` - The file type of the book.
+/// * `title` - The title of the entry, including any markup.
+/// * `anchor` - The anchor of the title's heading, for markdown.
+fn contents_item(file_type: &EntryFileType, title: &str, anchor: &str) -> String {
+    let text = file_type.strip_superscript(title);
+    match file_type {
+        EntryFileType::MarkdownDiary(_) => format!("- [{}](#{})", title, anchor),
+        EntryFileType::RstDiary(_) => format!("- `{}`_", text),
+        EntryFileType::OrgDiary(_) => format!("- [[*{}][{}]]", title, text),
+        _ => format!("- {}", text),
+    }
+}
+
+/// Stitches the entries within a range of dates into one document of the diary's file type.
+///
+/// The document is titled after the range and starts with a table of contents, with
+/// each entry's title and sections one heading level below it. Entries without a
+/// title are included but left out of the contents. Entries with a name
+/// have it added to their title.
+///
+/// # Arguments
+///
+/// * `opts` - The options passed by the user at runtime.
+/// * `diary` - Struct representing the diary.
+///
+/// # Returns
+///
+/// The document, or a DiaryError if there are no entries in the range or on any
+/// IO issues.
+pub fn book(opts: &BookOptions, diary: &Diary) -> Result<String, DiaryError> {
+    let file_type = diary.file_type();
+    let title = book_title(diary, opts.range);
+    let mut anchors = Anchors::default();
+    anchors.next(&title);
+    anchors.next("Contents");
+
+    let mut entries = 0;
+    let mut contents = vec![];
+    let mut body = String::new();
+    for entry_file in diary.entries()? {
+        if !opts.range.contains(&entry_file.date) {
+            continue;
+        }
+        entries += 1;
+        let content = fs::read_to_string(&entry_file.path)?;
+        let lines: Vec<&str> = content.lines().collect();
+        let mut i = file_type
+            .read_front_matter(&lines)
+            .map_or(0, |(_, span)| span);
+        let mut titled = false;
+
+        while i < lines.len() {
+            let Some((text, level, span)) = file_type.read_heading(&lines[i..]) else {
+                body.push_str(lines[i]);
+                body.push('\n');
+                i += 1;
+                continue;
+            };
+            let text = match &entry_file.name {
+                Some(name) if level == 1 && !titled => format!("{} ({})", text, name),
+                _ => text,
+            };
+            let anchor = anchors.next(&file_type.strip_superscript(&text));
+            if level == 1 && !titled {
+                contents.push(contents_item(file_type, &text, &anchor));
+                titled = true;
+            }
+            body.push_str(
+                file_type
+                    .heading(&text, demote(file_type, level))
+                    .trim_end(),
+            );
+            body.push('\n');
+            i += span;
+        }
+        if !body.ends_with("\n\n") {
+            body.push('\n');
+        }
+    }
+
+    if entries == 0 {
+        return Err(DiaryError::NoEntry { source: None });
+    }
+    let mut document = file_type.heading(&title, 1);
+    if !contents.is_empty() {
+        document.push_str(&file_type.heading("Contents", demote(file_type, 1)));
+        document.push_str(&contents.join("\n"));
+        document.push_str("\n\n");
+    }
+    document.push_str(&body);
+    Ok(document.trim_end().to_owned() + "\n")
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use chrono::prelude::*;
+
+    use super::{book, BookOptions};
+    use crate::{config::ConfigBuilder, ops::testing, utils::date::DateRange, Diary};
+
+    fn range() -> DateRange {
+        DateRange {
+            from: NaiveDate::from_ymd_opt(2021, 11, 6),
+            to: NaiveDate::from_ymd_opt(2021, 11, 7),
+        }
+    }
+
+    #[test]
+    fn md_book() {
+        let config = testing::diary_with_entries(&[
+            (
+                Local.with_ymd_and_hms(2021, 11, 5, 0, 0, 0).unwrap(),
+                "work",
+                "Day 5.",
+            ),
+            (
+                Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap(),
+                "work",
+                "Day 6.",
+            ),
+            (
+                Local.with_ymd_and_hms(2021, 11, 7, 0, 0, 0).unwrap(),
+                "work",
+                "Day 7.",
+            ),
+        ]);
+        let diary = Diary::from_config(&config).unwrap();
+
+        let opts = BookOptions { range: range() };
+        let document = book(&opts, &diary).unwrap();
+
+        assert_eq!(
+            document,
+            "# Diary, 2021-11-06 to 2021-11-07\n\n\
+             ## Contents\n\n\
+             - [Saturday 6<sup>th</sup> November 2021](#saturday-6th-november-2021)\n\
+             - [Sunday 7<sup>th</sup> November 2021](#sunday-7th-november-2021)\n\n\
+             ## Saturday 6<sup>th</sup> November 2021\n\n\
+             ### work\n\nDay 6.\n\n\
+             ## Sunday 7<sup>th</sup> November 2021\n\n\
+             ### work\n\nDay 7.\n"
+        );
+    }
+
+    #[test]
+    fn rst_book() {
+        let config = testing::temp_config();
+        let config = ConfigBuilder::from(config).file_type("rst").build();
+        testing::default_init(config.diary_path());
+        let diary = Diary::from_config(&config).unwrap();
+        let entry_date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+        testing::add_entries(&config, &[(entry_date, "work", "Notes.")]);
+
+        let opts = BookOptions { range: range() };
+        let document = book(&opts, &diary).unwrap();
+        let lines: Vec<&str> = document.lines().collect();
+
+        assert_eq!(lines[0], "Diary, 2021-11-06 to 2021-11-07");
+        assert_eq!(lines[1], "=".repeat(31));
+        assert_eq!(lines[3..5], ["Contents", "^^^^^^^^"]);
+        assert_eq!(lines[6], "- `Saturday 6th November 2021`_");
+        assert_eq!(lines[8], "Saturday 6\\ :sup:`th` November 2021");
+        assert_eq!(lines[9], "^".repeat(35));
+        assert_eq!(lines[11..13], ["work", "~~~~"]);
+    }
+
+    #[test]
+    fn txt_book() {
+        let config = testing::temp_config();
+        let config = ConfigBuilder::from(config).file_type("txt").build();
+        testing::default_init(config.diary_path());
+        let diary = Diary::from_config(&config).unwrap();
+        let entry_date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+        testing::add_entries(&config, &[(entry_date, "work", "Notes.")]);
+
+        let opts = BookOptions { range: range() };
+        let document = book(&opts, &diary).unwrap();
+        let lines: Vec<&str> = document.lines().collect();
+
+        assert_eq!(lines[1], "=".repeat(31));
+        assert_eq!(lines[3..5], ["Contents", "--------"]);
+        assert_eq!(lines[8], "Saturday 6th November 2021");
+        assert_eq!(lines[9], "-".repeat(26));
+        assert_eq!(lines[11], "[work]");
+    }
+
+    #[test]
+    fn rst_book_deep_headings() {
+        let config = testing::temp_config();
+        let config = ConfigBuilder::from(config).file_type("rst").build();
+        testing::default_init(config.diary_path());
+        let diary = Diary::from_config(&config).unwrap();
+        let entry_date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+        testing::new_entry(&config, &entry_date);
+        let path = diary.get_entry_path(&entry_date);
+        let content = fs::read_to_string(&path).unwrap() + "Detail\n\"\"\"\"\"\"\n\nMore.\n";
+        fs::write(&path, content).unwrap();
+
+        let opts = BookOptions { range: range() };
+        let document = book(&opts, &diary).unwrap();
+
+        assert!(document.contains("Detail\n''''''\n"));
+    }
+
+    #[test]
+    fn book_without_titles() {
+        let config = testing::temp_config();
+        testing::default_init(config.diary_path());
+        let diary = Diary::from_config(&config).unwrap();
+        let entry_date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+        fs::create_dir_all(diary.get_entry_path(&entry_date).parent().unwrap()).unwrap();
+        fs::write(diary.get_entry_path(&entry_date), "Untitled notes.\n").unwrap();
+
+        let opts = BookOptions { range: range() };
+        let document = book(&opts, &diary).unwrap();
+
+        assert_eq!(
+            document,
+            "# Diary, 2021-11-06 to 2021-11-07\n\nUntitled notes.\n"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "value: NoEntry")]
+    fn empty_book() {
+        let config = testing::temp_config();
+        testing::default_init(config.diary_path());
+        let diary = Diary::from_config(&config).unwrap();
+
+        let opts = BookOptions { range: range() };
+        book(&opts, &diary).unwrap();
+    }
+}
@@ -0,0 +1,601 @@
+//! # EPUB export
+//!
+//! Exports the entries within a range of dates as an EPUB 3 book, with a chapter for
+//! each month and a section for each entry.
+use std::{
+    collections::BTreeMap,
+    fs::{self, File},
+    io::Write,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+use chrono::prelude::*;
+use regex::{Captures, Regex};
+use zip::{write::FileOptions, CompressionMethod, ZipWriter};
+
+use super::book::{book_title, Anchors};
+use crate::{
+    errors::DiaryError,
+    utils::{date::DateRange, html},
+    Diary, EntryContent,
+};
+
+/// The folder of the archive holding the book's content.
+const CONTENT_FOLDER: &str = "OEBPS";
+
+/// Points reading systems to the package document.
+const CONTAINER: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<container version="1.0" xmlns="urn:oasis:names:tc:opendocument:xmlns:container">
+<rootfiles>
+<rootfile full-path="OEBPS/content.opf" media-type="application/oebps-package+xml"/>
+</rootfiles>
+</container>
+"#;
+
+/// The stylesheet shared by every chapter.
+const STYLE: &str = "body { line-height: 1.4; }
+h1 { page-break-before: always; }
+pre { white-space: pre-wrap; }
+img { max-width: 100%; }
+";
+
+/// The options available to the EPUB export.
+pub struct EpubOptions {
+    /// The dates of the entries to include.
+    pub range: DateRange,
+    /// The file to write the book to.
+    pub output: PathBuf,
+}
+
+/// What an EPUB export wrote.
+#[derive(Debug, PartialEq, Eq)]
+pub struct EpubExport {
+    /// The number of chapters, one for each month.
+    pub chapters: usize,
+    /// The number of entries.
+    pub entries: usize,
+    /// The number of images embedded from the diary folder.
+    pub images: usize,
+}
+
+/// A month of entries.
+struct Chapter {
+    /// The name of the chapter's file in the content folder.
+    file_name: String,
+    title: String,
+    /// The id and plain title of each entry's section.
+    entries: Vec<(String, String)>,
+    /// The XHTML of the entries.
+    body: String,
+}
+
+/// The media type of an image, or None if it is not an image type EPUB supports.
+fn image_media_type(path: &Path) -> Option<&'static str> {
+    let extension = path.extension()?.to_str()?.to_lowercase();
+    match extension.as_str() {
+        "png" => Some("image/png"),
+        "jpg" | "jpeg" => Some("image/jpeg"),
+        "gif" => Some("image/gif"),
+        "svg" => Some("image/svg+xml"),
+        "webp" => Some("image/webp"),
+        _ => None,
+    }
+}
+
+/// Moves every heading one level down, so entry titles sit below the chapter title.
+fn demote_headings(html: &str) -> String {
+    static HEADING: OnceLock<Regex> = OnceLock::new();
+    let heading = HEADING
+        .get_or_init(|| Regex::new(r"<(/?)h([1-5])\b").expect("The heading pattern is valid."));
+    heading
+        .replace_all(html, |captures: &Captures| {
+            let level: usize = captures[2].parse().unwrap_or(5);
+            format!("<{}h{}", &captures[1], level + 1)
+        })
+        .into_owned()
+}
+
+/// Points the images of an entry found in the diary folder at copies in the book,
+/// collecting the images to embed. Any other image would be missing from the book,
+/// so it is replaced by its alt text.
+///
+/// # Arguments
+///
+/// * `html` - The rendered entry.
+/// * `entry_folder` - The folder of the entry file, which image paths are relative to.
+/// * `diary_path` - The diary folder.
+/// * `images` - The images to embed, by their path in the content folder.
+fn embed_images(
+    html: &str,
+    entry_folder: &Path,
+    diary_path: &Path,
+    images: &mut BTreeMap<String, PathBuf>,
+) -> String {
+    static IMAGE: OnceLock<Regex> = OnceLock::new();
+    static SOURCE: OnceLock<Regex> = OnceLock::new();
+    static ALT: OnceLock<Regex> = OnceLock::new();
+    let image =
+        IMAGE.get_or_init(|| Regex::new(r"(?i)<img\b[^>]*>").expect("The image pattern is valid."));
+    let source = SOURCE.get_or_init(|| {
+        Regex::new(r#"(?i)\bsrc="([^"]*)""#).expect("The source pattern is valid.")
+    });
+    let alt = ALT
+        .get_or_init(|| Regex::new(r#"(?i)\balt="([^"]*)""#).expect("The alt pattern is valid."));
+    image
+        .replace_all(html, |captures: &Captures| {
+            let tag = &captures[0];
+            let path = source
+                .captures(tag)
+                .map(|source| entry_folder.join(&source[1]));
+            let relative = path
+                .as_ref()
+                .and_then(|path| path.canonicalize().ok())
+                .filter(|path| path.is_file() && image_media_type(path).is_some())
+                .and_then(|path| {
+                    let diary_path = diary_path.canonicalize().ok()?;
+                    path.strip_prefix(diary_path).ok().map(Path::to_path_buf)
+                });
+            let (Some(path), Some(relative)) = (path, relative) else {
+                return alt
+                    .captures(tag)
+                    .map(|alt| alt[1].to_owned())
+                    .unwrap_or_default();
+            };
+            let parts: Vec<String> = relative
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy().into_owned())
+                .collect();
+            let href = format!("media/{}", parts.join("/"));
+            images.insert(href.clone(), path);
+            source
+                .replace(tag, format!(r#"src="{}""#, html::escape(&href)).as_str())
+                .into_owned()
+        })
+        .into_owned()
+}
+
+/// Wraps a body in an XHTML document.
+fn xhtml(language: &str, title: &str, body: &str) -> String {
+    format!(
+        "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<!DOCTYPE html>\n\
+         <html xmlns=\"http://www.w3.org/1999/xhtml\" xmlns:epub=\"http://www.idpf.org/2007/ops\" \
+         xml:lang=\"{lang}\" lang=\"{lang}\">\n<head>\n<meta charset=\"utf-8\"/>\n<title>{}</title>\n\
+         <link rel=\"stylesheet\" type=\"text/css\" href=\"style.css\"/>\n</head>\n<body>\n{}</body>\n\
+         </html>\n",
+        html::escape(title),
+        body,
+        lang = language
+    )
+}
+
+/// Writes the navigation document, listing each chapter and its entries.
+fn navigation(language: &str, chapters: &[Chapter]) -> String {
+    let mut body = String::from("<nav epub:type=\"toc\" id=\"toc\">\n<h1>Contents</h1>\n<ol>\n");
+    for chapter in chapters {
+        body.push_str(&format!(
+            "<li><a href=\"{}\">{}</a>\n<ol>\n",
+            chapter.file_name,
+            html::escape(&chapter.title)
+        ));
+        for (id, title) in &chapter.entries {
+            body.push_str(&format!(
+                "<li><a href=\"{}#{}\">{}</a></li>\n",
+                chapter.file_name,
+                id,
+                html::escape(title)
+            ));
+        }
+        body.push_str("</ol>\n</li>\n");
+    }
+    body.push_str("</ol>\n</nav>\n");
+    xhtml(language, "Contents", &body)
+}
+
+/// A 64-bit FNV-1a hash, which unlike `DefaultHasher` stays the same across Rust
+/// releases, so re-exports of a book keep their identifier.
+fn stable_hash(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Writes the package document, holding the book's metadata, the files it is made of
+/// and their reading order.
+fn package(
+    diary: &Diary,
+    title: &str,
+    chapters: &[Chapter],
+    images: &BTreeMap<String, PathBuf>,
+) -> String {
+    let identity = format!("{}\0{}", diary.diary_path().display(), title);
+    let language = diary.locale().code();
+
+    let mut metadata = format!(
+        "<dc:identifier id=\"book-id\">urn:diary:{:016x}</dc:identifier>\n\
+         <dc:title>{}</dc:title>\n<dc:language>{}</dc:language>\n",
+        stable_hash(identity.as_bytes()),
+        html::escape(title),
+        language
+    );
+    if let Some(author) = diary.author() {
+        metadata.push_str(&format!(
+            "<dc:creator>{}</dc:creator>\n",
+            html::escape(author)
+        ));
+    }
+    metadata.push_str(&format!(
+        "<meta property=\"dcterms:modified\">{}</meta>\n",
+        Utc::now().format("%Y-%m-%dT%H:%M:%SZ")
+    ));
+
+    let mut manifest = String::from(
+        "<item id=\"nav\" href=\"nav.xhtml\" media-type=\"application/xhtml+xml\" properties=\"nav\"/>\n\
+         <item id=\"style\" href=\"style.css\" media-type=\"text/css\"/>\n",
+    );
+    let mut spine = String::new();
+    for (i, chapter) in chapters.iter().enumerate() {
+        manifest.push_str(&format!(
+            "<item id=\"chapter-{}\" href=\"{}\" media-type=\"application/xhtml+xml\"/>\n",
+            i + 1,
+            chapter.file_name
+        ));
+        spine.push_str(&format!("<itemref idref=\"chapter-{}\"/>\n", i + 1));
+    }
+    for (i, (href, path)) in images.iter().enumerate() {
+        manifest.push_str(&format!(
+            "<item id=\"image-{}\" href=\"{}\" media-type=\"{}\"/>\n",
+            i + 1,
+            html::escape(href),
+            image_media_type(path).unwrap_or_default()
+        ));
+    }
+
+    format!(
+        "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n\
+         <package xmlns=\"http://www.idpf.org/2007/opf\" version=\"3.0\" \
+         unique-identifier=\"book-id\" xml:lang=\"{}\">\n\
+         <metadata xmlns:dc=\"http://purl.org/dc/elements/1.1/\">\n{}</metadata>\n\
+         <manifest>\n{}</manifest>\n<spine>\n{}</spine>\n</package>\n",
+        language, metadata, manifest, spine
+    )
+}
+
+/// Exports the entries within a range of dates as an EPUB 3 book.
+///
+/// Each month is a chapter holding a section for each of its entries, listed in the
+/// book's navigation document. The title, author and language of the book come from
+/// the config, and images in the diary folder shown in entries are embedded.
+///
+/// # Arguments
+///
+/// * `opts` - The options passed by the user at runtime.
+/// * `diary` - Struct representing the diary.
+///
+/// # Returns
+///
+/// What was written, or a DiaryError if there are no entries in the range or on any
+/// IO issues.
+pub fn export_epub(opts: &EpubOptions, diary: &Diary) -> Result<EpubExport, DiaryError> {
+    let file_type = diary.file_type();
+    let language = diary.locale().code();
+    let mut chapters: Vec<Chapter> = vec![];
+    let mut images = BTreeMap::new();
+    let mut anchors = Anchors::default();
+    let mut entries = 0;
+
+    for entry_file in diary.entries()? {
+        if !opts.range.contains(&entry_file.date) {
+            continue;
+        }
+        let content = fs::read_to_string(&entry_file.path)?;
+        let entry = file_type.parse(&entry_file.date, &content);
+        let date = entry_file.date;
+
+        let file_name = date.format("%Y-%m.xhtml").to_string();
+        if chapters.last().map(|chapter| &chapter.file_name) != Some(&file_name) {
+            chapters.push(Chapter {
+                file_name,
+                title: format!("{} {}", diary.locale().month(&date), date.year()),
+                entries: vec![],
+                body: String::new(),
+            });
+        }
+        let Some(chapter) = chapters.last_mut() else {
+            continue;
+        };
+
+        let mut id = format!("entry-{}", date.format("%Y-%m-%d"));
+        let mut title = file_type.strip_superscript(&entry.title);
+        if let Some(name) = &entry_file.name {
+            id = format!("{}-{}", id, name);
+            title = format!("{} ({})", title, name);
+        }
+        let id = anchors.next(&id);
+        let entry_folder = entry_file.path.parent().unwrap_or_else(|| Path::new(""));
+        let body = embed_images(
+            &demote_headings(&html::close_void_elements(&html::render_entry(
+                file_type, &content,
+            ))),
+            entry_folder,
+            diary.diary_path(),
+            &mut images,
+        );
+        chapter
+            .body
+            .push_str(&format!("<section id=\"{}\">\n{}</section>\n", id, body));
+        chapter.entries.push((id, title));
+        entries += 1;
+    }
+
+    if chapters.is_empty() {
+        return Err(DiaryError::NoEntry { source: None });
+    }
+    let title = book_title(diary, opts.range);
+
+    let mut zip = ZipWriter::new(File::create(&opts.output)?);
+    let stored = FileOptions::default().compression_method(CompressionMethod::Stored);
+    let deflated = FileOptions::default().compression_method(CompressionMethod::Deflated);
+    // The mimetype must come first and uncompressed, so the archive can be recognised.
+    zip.start_file("mimetype", stored)?;
+    zip.write_all(b"application/epub+zip")?;
+    zip.start_file("META-INF/container.xml", deflated)?;
+    zip.write_all(CONTAINER.as_bytes())?;
+
+    let mut files = vec![
+        (
+            "content.opf".to_owned(),
+            package(diary, &title, &chapters, &images),
+        ),
+        ("nav.xhtml".to_owned(), navigation(language, &chapters)),
+        ("style.css".to_owned(), STYLE.to_owned()),
+    ];
+    for chapter in &chapters {
+        let body = format!(
+            "<section epub:type=\"chapter\">\n<h1>{}</h1>\n{}</section>\n",
+            html::escape(&chapter.title),
+            chapter.body
+        );
+        files.push((
+            chapter.file_name.clone(),
+            xhtml(language, &chapter.title, &body),
+        ));
+    }
+    for (name, content) in files {
+        zip.start_file(format!("{}/{}", CONTENT_FOLDER, name), deflated)?;
+        zip.write_all(content.as_bytes())?;
+    }
+    for (href, path) in &images {
+        zip.start_file(format!("{}/{}", CONTENT_FOLDER, href), deflated)?;
+        zip.write_all(&fs::read(path)?)?;
+    }
+    zip.finish()?;
+
+    Ok(EpubExport {
+        chapters: chapters.len(),
+        entries,
+        images: images.len(),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use std::{collections::HashSet, fs, io::Read};
+
+    use chrono::prelude::*;
+    use zip::{CompressionMethod, ZipArchive};
+
+    use super::{demote_headings, export_epub, stable_hash, EpubOptions};
+    use crate::{
+        config::ConfigBuilder,
+        ops::{
+            new::{new, NewOptions},
+            testing,
+        },
+        utils::{date::DateRange, editing::test::test_string_getter},
+        Diary,
+    };
+
+    fn read_file(archive: &mut ZipArchive<fs::File>, name: &str) -> String {
+        let mut content = String::new();
+        archive
+            .by_name(name)
+            .unwrap()
+            .read_to_string(&mut content)
+            .unwrap();
+        content
+    }
+
+    #[test]
+    fn demote() {
+        assert_eq!(
+            demote_headings("<h1>Title</h1>\n<h2 id=\"x\">Tag</h2>\n<h6>Deep</h6>\n<hr />"),
+            "<h2>Title</h2>\n<h3 id=\"x\">Tag</h3>\n<h6>Deep</h6>\n<hr />"
+        );
+    }
+
+    #[test]
+    fn fnv_hash() {
+        assert_eq!(stable_hash(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(stable_hash(b"a"), 0xaf63_dc4c_8601_ec8c);
+    }
+
+    #[test]
+    fn export_book() {
+        let config = testing::temp_config();
+        let config = ConfigBuilder::from(config)
+            .book_title("My Journal")
+            .author("A. Writer")
+            .locale("fr")
+            .build();
+        testing::default_init(config.diary_path());
+        let content = "A & B, ![photo](photo.png) <br>";
+        testing::add_entries(
+            &config,
+            &[
+                (
+                    Local.with_ymd_and_hms(2021, 10, 31, 0, 0, 0).unwrap(),
+                    "work",
+                    content,
+                ),
+                (
+                    Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap(),
+                    "work",
+                    content,
+                ),
+                (
+                    Local.with_ymd_and_hms(2021, 11, 7, 0, 0, 0).unwrap(),
+                    "work",
+                    content,
+                ),
+            ],
+        );
+        let diary = Diary::from_config(&config).unwrap();
+        fs::write(config.diary_path().join("2021-11/photo.png"), "png").unwrap();
+
+        let output = testing::temp_path().with_extension("epub");
+        let opts = EpubOptions {
+            range: DateRange::year(2021).unwrap(),
+            output: output.clone(),
+        };
+        let export = export_epub(&opts, &diary).unwrap();
+
+        assert_eq!(export.chapters, 2);
+        assert_eq!(export.entries, 3);
+        assert_eq!(export.images, 1);
+
+        let mut archive = ZipArchive::new(fs::File::open(&output).unwrap()).unwrap();
+        {
+            let mimetype = archive.by_index(0).unwrap();
+            assert_eq!(mimetype.name(), "mimetype");
+            assert_eq!(mimetype.compression(), CompressionMethod::Stored);
+        }
+        assert_eq!(read_file(&mut archive, "mimetype"), "application/epub+zip");
+
+        let container = read_file(&mut archive, "META-INF/container.xml");
+        let container = roxmltree::Document::parse(&container).unwrap();
+        let rootfile = container
+            .descendants()
+            .find(|node| node.has_tag_name("rootfile"))
+            .unwrap();
+        let package_path = rootfile.attribute("full-path").unwrap();
+
+        let package = read_file(&mut archive, package_path);
+        let package = roxmltree::Document::parse(&package).unwrap();
+        let root = package.root_element();
+        assert_eq!(root.attribute("version"), Some("3.0"));
+        let text = |name: &str| {
+            package
+                .descendants()
+                .find(|node| node.has_tag_name(name))
+                .and_then(|node| node.text())
+        };
+        assert_eq!(text("title"), Some("My Journal, 2021-01-01 to 2021-12-31"));
+        assert_eq!(text("creator"), Some("A. Writer"));
+        assert_eq!(text("language"), Some("fr"));
+        let identifier = package
+            .descendants()
+            .find(|node| node.has_tag_name("identifier"))
+            .unwrap();
+        assert_eq!(
+            identifier.attribute("id"),
+            root.attribute("unique-identifier")
+        );
+        assert!(text("meta").unwrap().ends_with('Z'));
+
+        let items: Vec<_> = package
+            .descendants()
+            .filter(|node| node.has_tag_name("item"))
+            .collect();
+        let ids: HashSet<&str> = items
+            .iter()
+            .filter_map(|item| item.attribute("id"))
+            .collect();
+        let hrefs: HashSet<&str> = items
+            .iter()
+            .filter_map(|item| item.attribute("href"))
+            .collect();
+        let navs = items
+            .iter()
+            .filter(|item| item.attribute("properties") == Some("nav"))
+            .count();
+        assert_eq!(navs, 1);
+        for item in &items {
+            let name = format!("OEBPS/{}", item.attribute("href").unwrap());
+            let content = read_file(&mut archive, &name);
+            if item.attribute("media-type") == Some("application/xhtml+xml") {
+                let document = roxmltree::Document::parse(&content).unwrap();
+                for image in document
+                    .descendants()
+                    .filter(|node| node.has_tag_name("img"))
+                {
+                    assert!(hrefs.contains(image.attribute("src").unwrap()));
+                }
+            }
+        }
+        let spine: Vec<&str> = package
+            .descendants()
+            .filter(|node| node.has_tag_name("itemref"))
+            .filter_map(|node| node.attribute("idref"))
+            .collect();
+        assert_eq!(spine.len(), 2);
+        assert!(spine.iter().all(|idref| ids.contains(idref)));
+
+        let nav = read_file(&mut archive, "OEBPS/nav.xhtml");
+        assert!(nav.contains("<a href=\"2021-10.xhtml\">octobre 2021</a>"));
+        assert!(
+            nav.contains("<a href=\"2021-11.xhtml#entry-2021-11-06\">samedi 6 novembre 2021</a>")
+        );
+        let chapter = read_file(&mut archive, "OEBPS/2021-11.xhtml");
+        assert!(chapter.contains("<h1>novembre 2021</h1>"));
+        assert!(chapter.contains("<section id=\"entry-2021-11-07\">\n<h2>"));
+        assert!(chapter.contains("<img src=\"media/2021-11/photo.png\""));
+        let october = read_file(&mut archive, "OEBPS/2021-10.xhtml");
+        assert!(!october.contains("<img"));
+        assert!(october.contains("A &amp; B, photo"));
+
+        let identifier = identifier.text().unwrap().to_owned();
+        export_epub(&opts, &diary).unwrap();
+        let mut archive = ZipArchive::new(fs::File::open(&output).unwrap()).unwrap();
+        let package = read_file(&mut archive, package_path);
+        assert!(package.contains(&format!(">{}</dc:identifier>", identifier)));
+    }
+
+    #[test]
+    fn export_named_entry() {
+        let config = testing::temp_config();
+        testing::default_init(config.diary_path());
+        let diary = Diary::from_config(&config).unwrap();
+        let entry_date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+        testing::new_entry(&config, &entry_date);
+        let new_opts = NewOptions {
+            open: false,
+            template: None,
+            name: Some("Day-Trip".to_owned()),
+        };
+        new(&new_opts, &diary, &entry_date, test_string_getter).unwrap();
+
+        let output = testing::temp_path().with_extension("epub");
+        let opts = EpubOptions {
+            range: DateRange::year(2021).unwrap(),
+            output: output.clone(),
+        };
+        export_epub(&opts, &diary).unwrap();
+
+        let mut archive = ZipArchive::new(fs::File::open(&output).unwrap()).unwrap();
+        let nav = read_file(&mut archive, "OEBPS/nav.xhtml");
+        let chapter = read_file(&mut archive, "OEBPS/2021-11.xhtml");
+        let chapter = roxmltree::Document::parse(&chapter).unwrap();
+        let ids: HashSet<&str> = chapter
+            .descendants()
+            .filter(|node| node.has_tag_name("section"))
+            .filter_map(|node| node.attribute("id"))
+            .collect();
+
+        assert_eq!(
+            ids,
+            HashSet::from(["entry-2021-11-06", "entry-2021-11-06-day-trip"])
+        );
+        assert!(nav.contains("<a href=\"2021-11.xhtml#entry-2021-11-06-day-trip\">"));
+    }
+}
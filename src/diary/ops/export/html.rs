@@ -0,0 +1,540 @@
+//! # HTML export
+//!
+//! Exports the diary as a static site: a page for each entry, index pages for each
+//! month and year, a tag index and copies of the files in the diary folder that
+//! entries link to.
+use std::{
+    collections::{BTreeMap, BTreeSet, HashSet},
+    fs,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+use chrono::prelude::*;
+use regex::Regex;
+
+use super::book::Anchors;
+use crate::{errors::DiaryError, utils::html, Diary, EntryContent};
+
+/// The options available to the HTML export.
+pub struct HtmlOptions {
+    /// The folder to write the site to.
+    pub out_dir: PathBuf,
+}
+
+/// What an HTML export wrote.
+#[derive(Debug, PartialEq, Eq)]
+pub struct HtmlExport {
+    /// The number of entry pages written.
+    pub entries: usize,
+    /// The number of attachments copied.
+    pub attachments: usize,
+}
+
+/// The stylesheet shared by every page.
+const STYLE: &str = "body { max-width: 45em; margin: 2em auto; padding: 0 1em; \
+font-family: sans-serif; line-height: 1.5; }
+nav { display: flex; gap: 1em; border-bottom: 1px solid #ccc; padding-bottom: 0.5em; }
+nav .next { margin-left: auto; }
+pre { overflow-x: auto; background: #f4f4f4; padding: 0.5em; }
+";
+
+/// An entry page of the site.
+struct Page {
+    date: NaiveDate,
+    /// The title of the entry, without markup.
+    title: String,
+    tags: Vec<String>,
+    /// The path of the page, relative to the site folder.
+    path: PathBuf,
+    /// The HTML of the entry.
+    body: String,
+}
+
+/// The href of a page, relative to the page linking to it.
+fn href(from: &Path, to: &Path) -> String {
+    let from_folder = from.parent().unwrap_or_else(|| Path::new(""));
+    let relative = pathdiff::diff_paths(to, from_folder).unwrap_or_else(|| to.to_path_buf());
+    let parts: Vec<String> = relative
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy().into_owned())
+        .collect();
+    parts.join("/")
+}
+
+/// A link from one page to another.
+fn link(from: &Path, to: &Path, text: &str) -> String {
+    format!(
+        r#"<a href="{}">{}</a>"#,
+        html::escape(&href(from, to)),
+        html::escape(text)
+    )
+}
+
+/// The path of the index page of a month, relative to the site folder.
+fn month_path(date: NaiveDate) -> PathBuf {
+    PathBuf::from("months").join(date.format("%Y-%m.html").to_string())
+}
+
+/// The path of the index page of a year, relative to the site folder.
+fn year_path(year: i32) -> PathBuf {
+    PathBuf::from("years").join(format!("{}.html", year))
+}
+
+/// Wraps the body of a page in an HTML document.
+///
+/// # Arguments
+///
+/// * `path` - The path of the page, relative to the site folder.
+/// * `title` - The title of the page, without markup.
+/// * `nav` - The navigation links of the page.
+/// * `body` - The HTML of the page.
+fn document(path: &Path, title: &str, nav: &[String], body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n\
+         <link rel=\"stylesheet\" href=\"{}\">\n</head>\n<body>\n<nav>{}</nav>\n<main>\n{}</main>\n\
+         </body>\n</html>\n",
+        html::escape(title),
+        html::escape(&href(path, Path::new("style.css"))),
+        nav.join(" "),
+        body
+    )
+}
+
+/// Writes a page, creating its folder if needed.
+fn write_page(out_dir: &Path, path: &Path, content: &str) -> Result<(), DiaryError> {
+    let full_path = out_dir.join(path);
+    if let Some(folder) = full_path.parent() {
+        fs::create_dir_all(folder)?;
+    }
+    fs::write(full_path, content)?;
+    Ok(())
+}
+
+/// Reads every entry of the diary into a page.
+fn read_pages(diary: &Diary) -> Result<Vec<Page>, DiaryError> {
+    let mut pages = vec![];
+    for entry_file in diary.entries()? {
+        let content = fs::read_to_string(&entry_file.path)?;
+        let entry = diary.parse_entry(&entry_file.date, &content);
+        let relative_path = entry_file
+            .path
+            .strip_prefix(diary.diary_path())
+            .unwrap_or(&entry_file.path)
+            .with_extension("html");
+        let mut title = diary.file_type().strip_superscript(&entry.title);
+        if let Some(name) = &entry_file.name {
+            title = format!("{} ({})", title, name);
+        }
+
+        pages.push(Page {
+            date: entry_file.date.date_naive(),
+            title,
+            tags: entry.tags().into_iter().map(str::to_owned).collect(),
+            path: relative_path,
+            body: html::render_entry(diary.file_type(), &content),
+        });
+    }
+    Ok(pages)
+}
+
+/// Writes the page of each entry, linked to the entries before and after it.
+fn write_entry_pages(
+    out_dir: &Path,
+    pages: &[Page],
+    tag_ids: &BTreeMap<&str, String>,
+) -> Result<(), DiaryError> {
+    for (i, page) in pages.iter().enumerate() {
+        let mut nav = vec![
+            link(&page.path, Path::new("index.html"), "Index"),
+            link(
+                &page.path,
+                &month_path(page.date),
+                &page.date.format("%B %Y").to_string(),
+            ),
+        ];
+        if !page.tags.is_empty() {
+            let tag_links: Vec<String> = page
+                .tags
+                .iter()
+                .map(|tag| {
+                    format!(
+                        r##"<a href="{}#{}">{}</a>"##,
+                        html::escape(&href(&page.path, Path::new("tags.html"))),
+                        html::escape(&tag_ids[tag.as_str()]),
+                        html::escape(tag)
+                    )
+                })
+                .collect();
+            nav.push(format!("<span>Tags: {}</span>", tag_links.join(", ")));
+        }
+        if let Some(previous) = i.checked_sub(1).and_then(|i| pages.get(i)) {
+            nav.push(link(
+                &page.path,
+                &previous.path,
+                &format!("« {}", previous.title),
+            ));
+        }
+        if let Some(next) = pages.get(i + 1) {
+            nav.push(format!(
+                r#"<span class="next">{}</span>"#,
+                link(&page.path, &next.path, &format!("{} »", next.title))
+            ));
+        }
+        write_page(
+            out_dir,
+            &page.path,
+            &document(&page.path, &page.title, &nav, &page.body),
+        )?;
+    }
+    Ok(())
+}
+
+/// Writes the index pages of each month and year, and the site index.
+fn write_index_pages(out_dir: &Path, pages: &[Page]) -> Result<(), DiaryError> {
+    let mut months: BTreeMap<NaiveDate, Vec<&Page>> = BTreeMap::new();
+    for page in pages {
+        let month = page.date.with_day(1).unwrap_or(page.date);
+        months.entry(month).or_default().push(page);
+    }
+    let mut years: BTreeMap<i32, Vec<NaiveDate>> = BTreeMap::new();
+    for month in months.keys() {
+        years.entry(month.year()).or_default().push(*month);
+    }
+
+    let month_list: Vec<&NaiveDate> = months.keys().collect();
+    for (i, (month, month_pages)) in months.iter().enumerate() {
+        let path = month_path(*month);
+        let title = month.format("%B %Y").to_string();
+        let mut nav = vec![
+            link(&path, Path::new("index.html"), "Index"),
+            link(&path, &year_path(month.year()), &month.year().to_string()),
+        ];
+        if let Some(previous) = i.checked_sub(1).and_then(|i| month_list.get(i)) {
+            nav.push(link(
+                &path,
+                &month_path(**previous),
+                &format!("« {}", previous.format("%B %Y")),
+            ));
+        }
+        if let Some(next) = month_list.get(i + 1) {
+            nav.push(format!(
+                r#"<span class="next">{}</span>"#,
+                link(
+                    &path,
+                    &month_path(**next),
+                    &format!("{} »", next.format("%B %Y"))
+                )
+            ));
+        }
+        let items: String = month_pages
+            .iter()
+            .map(|page| format!("<li>{}</li>\n", link(&path, &page.path, &page.title)))
+            .collect();
+        let body = format!("<h1>{}</h1>\n<ul>\n{}</ul>\n", html::escape(&title), items);
+        write_page(out_dir, &path, &document(&path, &title, &nav, &body))?;
+    }
+
+    let year_list: Vec<&i32> = years.keys().collect();
+    for (i, (year, year_months)) in years.iter().enumerate() {
+        let path = year_path(*year);
+        let mut nav = vec![link(&path, Path::new("index.html"), "Index")];
+        if let Some(previous) = i.checked_sub(1).and_then(|i| year_list.get(i)) {
+            nav.push(link(
+                &path,
+                &year_path(**previous),
+                &format!("« {}", previous),
+            ));
+        }
+        if let Some(next) = year_list.get(i + 1) {
+            nav.push(format!(
+                r#"<span class="next">{}</span>"#,
+                link(&path, &year_path(**next), &format!("{} »", next))
+            ));
+        }
+        let items: String = year_months
+            .iter()
+            .map(|month| {
+                let text = format!("{} ({} entries)", month.format("%B"), months[month].len());
+                format!("<li>{}</li>\n", link(&path, &month_path(*month), &text))
+            })
+            .collect();
+        let body = format!("<h1>{}</h1>\n<ul>\n{}</ul>\n", year, items);
+        write_page(
+            out_dir,
+            &path,
+            &document(&path, &year.to_string(), &nav, &body),
+        )?;
+    }
+
+    let path = Path::new("index.html");
+    let nav = vec![link(path, Path::new("tags.html"), "Tags")];
+    let items: String = years
+        .keys()
+        .map(|year| {
+            format!(
+                "<li>{}</li>\n",
+                link(path, &year_path(*year), &year.to_string())
+            )
+        })
+        .collect();
+    let body = format!("<h1>Diary</h1>\n<ul>\n{}</ul>\n", items);
+    write_page(out_dir, path, &document(path, "Diary", &nav, &body))
+}
+
+/// The id of each tag's heading on the tag index, numbering tags that would
+/// otherwise share an id.
+fn tag_ids(pages: &[Page]) -> BTreeMap<&str, String> {
+    let tags: BTreeSet<&str> = pages
+        .iter()
+        .flat_map(|page| page.tags.iter().map(String::as_str))
+        .collect();
+    let mut anchors = Anchors::default();
+    tags.into_iter()
+        .map(|tag| (tag, anchors.next(&format!("tag {}", tag))))
+        .collect()
+}
+
+/// Writes the tag index, listing the entries using each tag.
+fn write_tag_index(
+    out_dir: &Path,
+    pages: &[Page],
+    tag_ids: &BTreeMap<&str, String>,
+) -> Result<(), DiaryError> {
+    let mut tags: BTreeMap<&str, Vec<&Page>> = BTreeMap::new();
+    for page in pages {
+        for tag in &page.tags {
+            tags.entry(tag).or_default().push(page);
+        }
+    }
+
+    let path = Path::new("tags.html");
+    let nav = vec![link(path, Path::new("index.html"), "Index")];
+    let mut body = String::from("<h1>Tags</h1>\n");
+    for (tag, tag_pages) in tags {
+        body.push_str(&format!(
+            "<h2 id=\"{}\">{}</h2>\n<ul>\n",
+            html::escape(&tag_ids[tag]),
+            html::escape(tag)
+        ));
+        for page in tag_pages {
+            body.push_str(&format!(
+                "<li>{}</li>\n",
+                link(path, &page.path, &page.title)
+            ));
+        }
+        body.push_str("</ul>\n");
+    }
+    write_page(out_dir, path, &document(path, "Tags", &nav, &body))
+}
+
+/// Decodes the percent-encoded bytes of a link, e.g. `%20` for a space.
+fn percent_decode(link: &str) -> String {
+    let bytes = link.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// The files of the diary folder that the pages link to or embed, leaving out
+/// entries, templates, links to other sites and anything outside the diary folder.
+fn linked_files(diary: &Diary, pages: &[Page]) -> Result<BTreeSet<PathBuf>, DiaryError> {
+    static LINK: OnceLock<Regex> = OnceLock::new();
+    let link = LINK.get_or_init(|| {
+        Regex::new(r#"(?i)\b(?:src|href)="([^"]*)""#).expect("The link pattern is valid.")
+    });
+    let entry_paths: HashSet<PathBuf> = diary
+        .entries()?
+        .into_iter()
+        .filter_map(|entry_file| entry_file.path.canonicalize().ok())
+        .collect();
+    let diary_path = diary.diary_path().canonicalize()?;
+    let templates_path = diary_path.join("templates");
+
+    let mut files = BTreeSet::new();
+    for page in pages {
+        let folder = page.path.parent().unwrap_or_else(|| Path::new(""));
+        for captures in link.captures_iter(&page.body) {
+            let target = captures[1].replace("&amp;", "&");
+            let target = target.split(['#', '?']).next().unwrap_or_default();
+            if target.is_empty() || target.starts_with('/') || target.contains(':') {
+                continue;
+            }
+            let Ok(path) = diary_path
+                .join(folder)
+                .join(percent_decode(target))
+                .canonicalize()
+            else {
+                continue;
+            };
+            if path.is_file()
+                && path.starts_with(&diary_path)
+                && !path.starts_with(&templates_path)
+                && !entry_paths.contains(&path)
+            {
+                files.insert(path);
+            }
+        }
+    }
+    Ok(files)
+}
+
+/// Copies the files of the diary folder that entries link to, keeping their paths.
+fn copy_attachments(diary: &Diary, pages: &[Page], out_dir: &Path) -> Result<usize, DiaryError> {
+    let diary_path = diary.diary_path().canonicalize()?;
+    let out_dir = out_dir.canonicalize()?;
+
+    let mut copied = 0;
+    for path in linked_files(diary, pages)? {
+        if path.starts_with(&out_dir) {
+            continue;
+        }
+        let relative_path = path.strip_prefix(&diary_path).unwrap_or(&path);
+        let target = out_dir.join(relative_path);
+        if let Some(folder) = target.parent() {
+            fs::create_dir_all(folder)?;
+        }
+        fs::copy(&path, target)?;
+        copied += 1;
+    }
+    Ok(copied)
+}
+
+/// Exports the diary as a static HTML site. Entry pages keep the paths of their
+/// entry files, so the attachments linked from entries, which are copied along with
+/// them, are found next to them.
+///
+/// # Arguments
+///
+/// * `opts` - The options passed by the user at runtime.
+/// * `diary` - Struct representing the diary.
+///
+/// # Returns
+///
+/// What was written, or a DiaryError on any IO issues.
+pub fn export_html(opts: &HtmlOptions, diary: &Diary) -> Result<HtmlExport, DiaryError> {
+    fs::create_dir_all(&opts.out_dir)?;
+    let pages = read_pages(diary)?;
+
+    let attachments = copy_attachments(diary, &pages, &opts.out_dir)?;
+    let tag_ids = tag_ids(&pages);
+    write_entry_pages(&opts.out_dir, &pages, &tag_ids)?;
+    write_index_pages(&opts.out_dir, &pages)?;
+    write_tag_index(&opts.out_dir, &pages, &tag_ids)?;
+    fs::write(opts.out_dir.join("style.css"), STYLE)?;
+
+    Ok(HtmlExport {
+        entries: pages.len(),
+        attachments,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use chrono::prelude::*;
+
+    use super::{export_html, tag_ids, HtmlOptions, Page};
+    use crate::{ops::testing, Diary};
+
+    #[test]
+    fn export_site() {
+        let config = testing::diary_with_entries(&[
+            (
+                Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap(),
+                "work",
+                "![photo](photo.jpg)",
+            ),
+            (
+                Local.with_ymd_and_hms(2021, 11, 7, 0, 0, 0).unwrap(),
+                "work",
+                "![photo](photo.jpg)",
+            ),
+        ]);
+        let diary = Diary::from_config(&config).unwrap();
+        fs::write(config.diary_path().join("2021-11/photo.jpg"), "jpeg").unwrap();
+        fs::write(config.diary_path().join("2021-11/notes.txt"), "private").unwrap();
+        fs::write(config.diary_path().join(".env"), "TOKEN=secret").unwrap();
+
+        let out_dir = testing::temp_path();
+        let opts = HtmlOptions {
+            out_dir: out_dir.clone(),
+        };
+        let export = export_html(&opts, &diary).unwrap();
+
+        assert_eq!(export.entries, 2);
+        assert_eq!(export.attachments, 1);
+        assert!(out_dir.join("2021-11/photo.jpg").exists());
+        assert!(!out_dir.join("2021-11/notes.txt").exists());
+        assert!(!out_dir.join(".env").exists());
+        assert!(out_dir.join("style.css").exists());
+
+        let first = fs::read_to_string(out_dir.join("2021-11/diary_2021-11-06.html")).unwrap();
+        assert!(first.contains("<h1>Saturday 6<sup>th</sup> November 2021</h1>"));
+        assert!(first.contains(r#"<img src="photo.jpg" alt="photo" />"#));
+        assert!(first.contains(r#"href="../months/2021-11.html""#));
+        assert!(first.contains(r#"<a href="diary_2021-11-07.html">Sunday 7th November 2021 »</a>"#));
+        assert!(first.contains(r#"href="../tags.html#tag-work""#));
+
+        let month = fs::read_to_string(out_dir.join("months/2021-11.html")).unwrap();
+        assert!(month.contains(r#"<a href="../2021-11/diary_2021-11-06.html">"#));
+        let year = fs::read_to_string(out_dir.join("years/2021.html")).unwrap();
+        assert!(year.contains("November (2 entries)"));
+        let index = fs::read_to_string(out_dir.join("index.html")).unwrap();
+        assert!(index.contains(r#"<a href="years/2021.html">2021</a>"#));
+        let tags = fs::read_to_string(out_dir.join("tags.html")).unwrap();
+        assert!(tags.contains(r#"<h2 id="tag-work">work</h2>"#));
+    }
+
+    #[test]
+    fn unique_tag_ids() {
+        let page = Page {
+            date: NaiveDate::from_ymd_opt(2021, 11, 6).unwrap(),
+            title: "Saturday 6th November 2021".to_owned(),
+            tags: vec!["Day off".to_owned(), "day-off".to_owned(), "C++".to_owned()],
+            path: "2021-11/diary_2021-11-06.html".into(),
+            body: String::new(),
+        };
+        let pages = [page];
+
+        let ids = tag_ids(&pages);
+
+        assert_eq!(ids["C++"], "tag-c");
+        assert_eq!(ids["Day off"], "tag-day-off");
+        assert_eq!(ids["day-off"], "tag-day-off-1");
+    }
+
+    #[test]
+    fn export_site_inside_diary() {
+        let config = testing::temp_config();
+        testing::default_init(config.diary_path());
+        let entry_date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+        testing::new_entry(&config, &entry_date);
+        let diary = Diary::from_config(&config).unwrap();
+
+        let opts = HtmlOptions {
+            out_dir: config.diary_path().join("site"),
+        };
+        export_html(&opts, &diary).unwrap();
+        let export = export_html(&opts, &diary).unwrap();
+
+        assert_eq!(export.attachments, 0);
+    }
+}
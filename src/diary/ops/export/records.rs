@@ -0,0 +1,227 @@
+//! # Record export
+//!
+//! Exports one record per entry, holding its date, title, sections and word counts,
+//! as JSON or CSV.
+use std::path::PathBuf;
+
+use chrono::prelude::*;
+
+use crate::{entry::Section, errors::DiaryError, utils::date::DateRange, Diary, EntryContent};
+
+/// The options available to the JSON and CSV exports.
+pub struct RecordOptions {
+    /// The dates of the entries to export.
+    pub range: DateRange,
+}
+
+/// A tagged section of an exported entry.
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct SectionRecord {
+    /// The tag heading the section.
+    pub tag: String,
+    /// The time the section was added, if it was timestamped.
+    pub time: Option<NaiveTime>,
+    /// The text of the section.
+    pub body: String,
+    /// The number of words in the body.
+    pub words: usize,
+}
+
+impl From<&Section> for SectionRecord {
+    fn from(section: &Section) -> Self {
+        Self {
+            tag: section.tag.clone(),
+            time: section.time,
+            body: section.body.clone(),
+            words: section.word_count(),
+        }
+    }
+}
+
+/// An exported entry.
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct EntryRecord {
+    /// The date of the entry.
+    pub date: NaiveDate,
+    /// The name of the entry, or None for the main entry of the day.
+    pub name: Option<String>,
+    /// The path of the entry file.
+    pub path: PathBuf,
+    /// The title of the entry, without markup such as superscript.
+    pub title: String,
+    /// The free text written before the first tag.
+    pub preamble: String,
+    /// The number of words in the preamble and sections.
+    pub words: usize,
+    /// The tagged sections, in the order they appear in the entry.
+    pub sections: Vec<SectionRecord>,
+}
+
+/// The header row of the CSV export, naming the fields of `CsvRow` in order.
+const CSV_HEADER: [&str; 8] = [
+    "date", "name", "path", "title", "tags", "words", "preamble", "sections",
+];
+
+/// A row of the CSV export. Sections don't fit in a single cell, so they are
+/// written as a JSON array, and the distinct tags are also given separately.
+#[derive(Serialize)]
+struct CsvRow<'a> {
+    date: NaiveDate,
+    name: &'a str,
+    path: String,
+    title: &'a str,
+    tags: String,
+    words: usize,
+    preamble: &'a str,
+    sections: String,
+}
+
+/// Reads the entries of the diary within a range of dates into records.
+///
+/// # Arguments
+///
+/// * `opts` - The options passed by the user at runtime.
+/// * `diary` - Struct representing the diary.
+///
+/// # Returns
+///
+/// A record of each entry sorted by date, or a DiaryError on any IO issues.
+pub fn records(opts: &RecordOptions, diary: &Diary) -> Result<Vec<EntryRecord>, DiaryError> {
+    let mut records = vec![];
+    for entry_file in diary.entries()? {
+        if !opts.range.contains(&entry_file.date) {
+            continue;
+        }
+        let entry = diary.read_entry(&entry_file)?;
+
+        records.push(EntryRecord {
+            date: entry_file.date.date_naive(),
+            name: entry_file.name,
+            path: entry_file.path,
+            title: diary.file_type().strip_superscript(&entry.title),
+            words: entry.word_count(),
+            sections: entry.sections.iter().map(SectionRecord::from).collect(),
+            preamble: entry.preamble,
+        });
+    }
+    Ok(records)
+}
+
+/// Writes records as a JSON array.
+pub fn to_json(records: &[EntryRecord]) -> Result<String, DiaryError> {
+    Ok(serde_json::to_string_pretty(records)? + "\n")
+}
+
+/// Writes records as CSV with a header row, one row per entry. The header is
+/// written even when there are no records.
+pub fn to_csv(records: &[EntryRecord]) -> Result<String, DiaryError> {
+    let mut writer = csv::WriterBuilder::new()
+        .has_headers(false)
+        .from_writer(vec![]);
+    writer.write_record(CSV_HEADER)?;
+    for record in records {
+        let mut tags: Vec<&str> = vec![];
+        for section in &record.sections {
+            if !section.tag.is_empty() && !tags.contains(&section.tag.as_str()) {
+                tags.push(&section.tag);
+            }
+        }
+        writer.serialize(CsvRow {
+            date: record.date,
+            name: record.name.as_deref().unwrap_or_default(),
+            path: record.path.display().to_string(),
+            title: &record.title,
+            tags: tags.join(";"),
+            words: record.words,
+            preamble: &record.preamble,
+            sections: serde_json::to_string(&record.sections)?,
+        })?;
+    }
+    let bytes = writer
+        .into_inner()
+        .map_err(|err| DiaryError::IOError(err.into_error()))?;
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::prelude::*;
+
+    use super::{records, to_csv, to_json, RecordOptions};
+    use crate::{ops::testing, utils::date::DateRange, Diary};
+
+    #[test]
+    fn entry_records() {
+        let first_date = Local.with_ymd_and_hms(2021, 11, 5, 0, 0, 0).unwrap();
+        let second_date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+        let config = testing::diary_with_entries(&[
+            (first_date, "work", "Wrote the report."),
+            (second_date, "home", "Built a \"shelf\", badly."),
+        ]);
+        let diary = Diary::from_config(&config).unwrap();
+        let opts = RecordOptions {
+            range: DateRange::day(NaiveDate::from_ymd_opt(2021, 11, 6).unwrap()),
+        };
+        let records = records(&opts, &diary).unwrap();
+
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].title, "Saturday 6th November 2021");
+        assert_eq!(records[0].words, 4);
+        assert_eq!(records[0].sections.len(), 1);
+        assert_eq!(records[0].sections[0].tag, "home");
+        assert_eq!(records[0].sections[0].body, "Built a \"shelf\", badly.");
+    }
+
+    #[test]
+    fn json_records() {
+        let first_date = Local.with_ymd_and_hms(2021, 11, 5, 0, 0, 0).unwrap();
+        let second_date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+        let config = testing::diary_with_entries(&[
+            (first_date, "work", "Wrote the report."),
+            (second_date, "home", "Built a \"shelf\", badly."),
+        ]);
+        let diary = Diary::from_config(&config).unwrap();
+        let opts = RecordOptions {
+            range: DateRange::default(),
+        };
+        let json = to_json(&records(&opts, &diary).unwrap()).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(value[0]["date"], "2021-11-05");
+        assert_eq!(value[0]["sections"][0]["tag"], "work");
+        assert_eq!(value[0]["sections"][0]["words"], 3);
+        assert_eq!(value[1]["title"], "Saturday 6th November 2021");
+    }
+
+    #[test]
+    fn csv_records() {
+        let first_date = Local.with_ymd_and_hms(2021, 11, 5, 0, 0, 0).unwrap();
+        let second_date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+        let config = testing::diary_with_entries(&[
+            (first_date, "work", "Wrote the report."),
+            (second_date, "home", "Built a \"shelf\", badly."),
+        ]);
+        let diary = Diary::from_config(&config).unwrap();
+        let opts = RecordOptions {
+            range: DateRange::default(),
+        };
+        let csv = to_csv(&records(&opts, &diary).unwrap()).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(
+            lines[0],
+            "date,name,path,title,tags,words,preamble,sections"
+        );
+        assert!(lines[1].starts_with("2021-11-05,,"));
+        assert!(lines[2].contains(",Saturday 6th November 2021,home,4,,"));
+        assert!(lines[2].contains(r#"""body"":""Built a \""shelf\"", badly.""#));
+    }
+
+    #[test]
+    fn csv_no_records() {
+        let csv = to_csv(&[]).unwrap();
+
+        assert_eq!(csv, "date,name,path,title,tags,words,preamble,sections\n");
+    }
+}
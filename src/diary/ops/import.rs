@@ -0,0 +1,327 @@
+//! # Import operations
+//!
+//! The import module contains functionality relating to the import command,
+//! independent of the CLI. Each journaling tool has a module reading its format into
+//! imported entries, which are written to the diary through the new and add operations.
+use std::{collections::BTreeSet, fs};
+
+use chrono::prelude::*;
+
+use crate::{
+    errors::DiaryError,
+    ops::{
+        add::{add, tag_front_matter, AddOptions},
+        new::{new, NewOptions},
+    },
+    utils::editing,
+    Diary,
+};
+
+pub mod dayone;
+pub mod jrnl;
+
+/// The options available to the import command.
+pub struct ImportOptions {
+    /// Whether to add to entries that already exist, rather than refusing to.
+    pub merge: bool,
+}
+
+/// An entry read from another journaling tool.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ImportedEntry {
+    /// When the entry was written.
+    pub date: DateTime<Local>,
+    /// The text of the entry.
+    pub text: String,
+    /// The tags of the entry, in the order the tool gives them.
+    pub tags: Vec<String>,
+}
+
+/// What an import wrote.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ImportSummary {
+    /// The number of entries imported.
+    pub imported: usize,
+    /// The number of entries skipped as the diary already holds them.
+    pub skipped: usize,
+    /// The number of diary entries created for them.
+    pub created: usize,
+}
+
+/// Adds imported entries to the diary in date order, creating the entry of each day
+/// that doesn't have one.
+///
+/// Each is added once with its time, under a heading for its first tag if it has any,
+/// and with a line listing its other tags below the text. Every tag is also listed in
+/// the front matter if the diary has it. An entry whose section is already in the
+/// diary is skipped, so an import that stopped part way can be run again with merging
+/// to finish it.
+///
+/// # Arguments
+///
+/// * `opts` - The options passed by the user at runtime.
+/// * `diary` - Struct representing the diary.
+/// * `entries` - The entries to import.
+/// * `string_getter` - The function that obtains the string to add to the file.
+///
+/// # Returns
+///
+/// What was imported. DiaryError, before anything is written, if a day already has an
+/// entry and merging wasn't asked for. DiaryError on any other IO issues.
+pub fn import(
+    opts: &ImportOptions,
+    diary: &Diary,
+    mut entries: Vec<ImportedEntry>,
+    string_getter: editing::StringGetter,
+) -> Result<ImportSummary, DiaryError> {
+    entries.sort_by_key(|entry| entry.date);
+    if !opts.merge {
+        if let Some(path) = entries
+            .iter()
+            .map(|entry| diary.get_entry_path(&entry.date))
+            .find(|path| path.exists())
+        {
+            return Err(DiaryError::ImportClash { path });
+        }
+    }
+
+    let mut created = BTreeSet::new();
+    let mut imported = 0;
+    for entry in &entries {
+        if !diary.get_entry_path(&entry.date).exists() {
+            let new_opts = NewOptions {
+                open: false,
+                template: None,
+                name: None,
+            };
+            new(&new_opts, diary, &entry.date, string_getter)?;
+            created.insert(entry.date.date_naive());
+        }
+        let text = section_text(entry);
+        if !already_imported(diary, entry, &text)? {
+            let add_opts = AddOptions {
+                tag: entry.tags.first().cloned(),
+                content: Some(text),
+                time: Some(entry.date.time()),
+                name: None,
+            };
+            add(&add_opts, diary, &entry.date, string_getter)?;
+            imported += 1;
+        }
+        let entry_path = diary.get_entry_path(&entry.date);
+        for tag in entry.tags.iter().skip(1) {
+            tag_front_matter(diary, &entry_path, tag)?;
+        }
+    }
+
+    Ok(ImportSummary {
+        imported,
+        skipped: entries.len() - imported,
+        created: created.len(),
+    })
+}
+
+/// The text of the section an imported entry is added as, its own text followed by
+/// a line listing the tags after the first, which heads the section.
+fn section_text(entry: &ImportedEntry) -> String {
+    match entry.tags.get(1..) {
+        Some(others) if !others.is_empty() => {
+            format!("{}\n\nTags: {}", entry.text, others.join(", "))
+        }
+        _ => entry.text.clone(),
+    }
+}
+
+/// Whether the entry of the imported entry's day already has its section, with its
+/// first tag, time and text, i.e. it was imported before.
+fn already_imported(diary: &Diary, entry: &ImportedEntry, text: &str) -> Result<bool, DiaryError> {
+    let entry_path = diary.get_entry_path(&entry.date);
+    if !entry_path.exists() {
+        return Ok(false);
+    }
+    let content = fs::read_to_string(entry_path)?;
+    let tag = entry.tags.first().map_or("", String::as_str);
+    let time = entry.date.format("%H:%M").to_string();
+
+    Ok(diary
+        .parse_entry(&entry.date, &content)
+        .sections
+        .iter()
+        .any(|section| {
+            section.tag == tag
+                && section
+                    .time
+                    .map(|time| time.format("%H:%M").to_string())
+                    .as_ref()
+                    == Some(&time)
+                && section.body == text
+        }))
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use chrono::prelude::*;
+
+    use super::{import, ImportOptions, ImportedEntry};
+    use crate::{
+        config::Config, ops::testing, utils::editing::test::test_string_getter, Diary, EntryContent,
+    };
+
+    fn imported(day: u32, hour: u32, text: &str, tags: &[&str]) -> ImportedEntry {
+        ImportedEntry {
+            date: Local.with_ymd_and_hms(2021, 11, day, hour, 30, 0).unwrap(),
+            text: text.to_owned(),
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn import_entries() {
+        let config = testing::temp_config();
+        testing::default_init(config.diary_path());
+        let diary = Diary::from_config(&config).unwrap();
+
+        let entries = vec![
+            imported(6, 18, "Evening walk.", &[]),
+            imported(6, 9, "Planned the release.", &["work", "planning"]),
+            imported(7, 10, "Reviewed it.", &["work"]),
+        ];
+        let summary = import(
+            &ImportOptions { merge: false },
+            &diary,
+            entries,
+            test_string_getter,
+        )
+        .unwrap();
+
+        assert_eq!(summary.imported, 3);
+        assert_eq!(summary.created, 2);
+
+        let date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+        let content = fs::read_to_string(diary.get_entry_path(&date)).unwrap();
+        let entry = diary.file_type().parse(&date, &content);
+        assert_eq!(entry.sections.len(), 2);
+        assert_eq!(entry.sections[0].tag, "work");
+        assert_eq!(entry.sections[0].time, NaiveTime::from_hms_opt(9, 30, 0));
+        assert_eq!(
+            entry.sections[0].body,
+            "Planned the release.\n\nTags: planning"
+        );
+        assert_eq!(entry.sections[1].tag, "");
+        assert_eq!(entry.sections[1].time, NaiveTime::from_hms_opt(18, 30, 0));
+        assert_eq!(entry.sections[1].body, "Evening walk.");
+    }
+
+    #[test]
+    fn import_tags_to_front_matter() {
+        let config = Config::builder()
+            .diary_path(testing::temp_diary_path())
+            .front_matter(true)
+            .build();
+        testing::default_init(config.diary_path());
+        let diary = Diary::from_config(&config).unwrap();
+
+        let entries = vec![imported(
+            6,
+            9,
+            "Planned the release.",
+            &["work", "planning"],
+        )];
+        import(
+            &ImportOptions { merge: false },
+            &diary,
+            entries,
+            test_string_getter,
+        )
+        .unwrap();
+
+        let date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+        let content = fs::read_to_string(diary.get_entry_path(&date)).unwrap();
+        let entry = diary.file_type().parse(&date, &content);
+        assert_eq!(entry.front_matter.unwrap().tags, ["work", "planning"]);
+        assert_eq!(entry.sections.len(), 1);
+        assert_eq!(entry.sections[0].tag, "work");
+        assert_eq!(
+            entry.sections[0].body,
+            "Planned the release.\n\nTags: planning"
+        );
+    }
+
+    #[test]
+    fn import_clash() {
+        let config = testing::temp_config();
+        testing::default_init(config.diary_path());
+        let diary = Diary::from_config(&config).unwrap();
+        let date = Local.with_ymd_and_hms(2021, 11, 7, 0, 0, 0).unwrap();
+        testing::new_entry(&config, &date);
+        let before = fs::read_to_string(diary.get_entry_path(&date)).unwrap();
+
+        let entries = vec![imported(6, 9, "One.", &[]), imported(7, 9, "Two.", &[])];
+        let result = import(
+            &ImportOptions { merge: false },
+            &diary,
+            entries,
+            test_string_getter,
+        );
+
+        assert!(result.is_err());
+        assert!(!diary
+            .get_entry_path(&Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap())
+            .exists());
+
+        let entries = vec![imported(6, 9, "One.", &[]), imported(7, 9, "Two.", &[])];
+        let summary = import(
+            &ImportOptions { merge: true },
+            &diary,
+            entries,
+            test_string_getter,
+        )
+        .unwrap();
+
+        assert_eq!(summary.created, 1);
+        let after = fs::read_to_string(diary.get_entry_path(&date)).unwrap();
+        assert!(after.starts_with(&before));
+        assert!(after.ends_with("Two.\n"));
+    }
+
+    #[test]
+    fn import_merge_skips_imported() {
+        let config = testing::temp_config();
+        testing::default_init(config.diary_path());
+        let diary = Diary::from_config(&config).unwrap();
+
+        let entries = vec![imported(6, 9, "One.", &["work", "planning"])];
+        import(
+            &ImportOptions { merge: false },
+            &diary,
+            entries,
+            test_string_getter,
+        )
+        .unwrap();
+
+        let entries = vec![
+            imported(6, 9, "One.", &["work", "planning"]),
+            imported(6, 10, "One.", &["work"]),
+            imported(7, 9, "Two.", &[]),
+        ];
+        let summary = import(
+            &ImportOptions { merge: true },
+            &diary,
+            entries,
+            test_string_getter,
+        )
+        .unwrap();
+
+        assert_eq!(summary.imported, 2);
+        assert_eq!(summary.skipped, 1);
+        assert_eq!(summary.created, 1);
+
+        let date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+        let content = fs::read_to_string(diary.get_entry_path(&date)).unwrap();
+        let entry = diary.file_type().parse(&date, &content);
+        assert_eq!(entry.sections.len(), 2);
+        assert_eq!(entry.sections[1].time, NaiveTime::from_hms_opt(10, 30, 0));
+    }
+}
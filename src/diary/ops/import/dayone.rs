@@ -0,0 +1,142 @@
+//! # Day One import
+//!
+//! Reads the JSON file of a Day One export, holding a list of entries with their
+//! creation date, time zone, markdown text and tags.
+use chrono::prelude::*;
+use chrono_tz::Tz;
+
+use super::ImportedEntry;
+use crate::errors::DiaryError;
+
+/// A Day One export.
+#[derive(Deserialize)]
+struct Export {
+    entries: Vec<Entry>,
+}
+
+/// An entry of a Day One export, leaving out the fields that aren't imported.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Entry {
+    /// When the entry was written, e.g. `2021-11-06T09:30:00Z`.
+    creation_date: String,
+    /// The time zone the entry was written in, e.g. `Europe/London`.
+    time_zone: Option<String>,
+    #[serde(default)]
+    text: String,
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+/// Reads the entries of a Day One export. Entries without text are left out, and each
+/// is dated by the clock of the time zone it was written in, if the export gives one.
+///
+/// # Arguments
+///
+/// * `content` - The content of the export's JSON file.
+///
+/// # Returns
+///
+/// The entries in the order they appear, or a DiaryError if the file isn't a Day One
+/// export or an entry has an invalid date or time zone.
+pub fn parse(content: &str) -> Result<Vec<ImportedEntry>, DiaryError> {
+    let export: Export = serde_json::from_str(content)?;
+    let mut entries = vec![];
+    for entry in export.entries {
+        let text = entry.text.trim().to_owned();
+        if text.is_empty() {
+            continue;
+        }
+        let date = DateTime::parse_from_rfc3339(&entry.creation_date).map_err(|_| {
+            DiaryError::BadImport {
+                reason: format!("invalid date `{}`", entry.creation_date),
+            }
+        })?;
+        let date = match entry.time_zone {
+            Some(name) => {
+                let time_zone: Tz = name.parse().map_err(|_| DiaryError::BadImport {
+                    reason: format!("invalid time zone `{}`", name),
+                })?;
+                let written = date.with_timezone(&time_zone).naive_local();
+                Local
+                    .from_local_datetime(&written)
+                    .earliest()
+                    .unwrap_or_else(|| date.with_timezone(&Local))
+            }
+            None => date.with_timezone(&Local),
+        };
+        entries.push(ImportedEntry {
+            date,
+            text,
+            tags: entry.tags,
+        });
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::prelude::*;
+
+    use super::parse;
+
+    #[test]
+    fn parse_dayone() {
+        let content = r#"{
+            "metadata": {"version": "1.0"},
+            "entries": [
+                {
+                    "uuid": "A1",
+                    "creationDate": "2021-11-06T09:30:00Z",
+                    "text": "Planned the release.\n",
+                    "tags": ["work", "planning"]
+                },
+                {"creationDate": "2021-11-07T10:00:00Z", "text": " "},
+                {"creationDate": "2021-11-08T10:00:00Z", "text": "Rest day."},
+                {
+                    "creationDate": "2021-11-09T03:15:00Z",
+                    "timeZone": "America/New_York",
+                    "text": "Late night."
+                }
+            ]
+        }"#;
+        let entries = parse(content).unwrap();
+
+        assert_eq!(entries.len(), 3);
+        assert_eq!(
+            entries[0].date,
+            Utc.with_ymd_and_hms(2021, 11, 6, 9, 30, 0)
+                .unwrap()
+                .with_timezone(&Local)
+        );
+        assert_eq!(entries[0].text, "Planned the release.");
+        assert_eq!(entries[0].tags, ["work", "planning"]);
+        assert!(entries[1].tags.is_empty());
+        assert_eq!(
+            entries[2].date.naive_local(),
+            NaiveDate::from_ymd_opt(2021, 11, 8)
+                .unwrap()
+                .and_hms_opt(22, 15, 0)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "value: BadImport")]
+    fn parse_bad_time_zone() {
+        parse(r#"{"entries": [{"creationDate": "2021-11-06T09:30:00Z", "timeZone": "Mars/Olympus", "text": "Oops."}]}"#)
+            .unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "value: BadImport")]
+    fn parse_bad_date() {
+        parse(r#"{"entries": [{"creationDate": "yesterday", "text": "Oops."}]}"#).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "value: JsonError")]
+    fn parse_not_dayone() {
+        parse("[]").unwrap();
+    }
+}
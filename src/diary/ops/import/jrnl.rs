@@ -0,0 +1,97 @@
+//! # jrnl import
+//!
+//! Reads the plain text format of jrnl, where each entry starts with a
+//! `[YYYY-MM-DD HH:MM]` header followed by its title, and tags are words starting
+//! with `@`.
+use chrono::prelude::*;
+use regex::Regex;
+
+use super::ImportedEntry;
+use crate::errors::DiaryError;
+
+/// Reads the entries of a jrnl file. The title and body of each entry are kept as
+/// its text, without the star marking starred entries.
+///
+/// # Arguments
+///
+/// * `content` - The content of the jrnl file.
+///
+/// # Returns
+///
+/// The entries in the order they appear, or a DiaryError if a header holds an
+/// invalid date.
+pub fn parse(content: &str) -> Result<Vec<ImportedEntry>, DiaryError> {
+    let header = Regex::new(r"^\[(\d{4}-\d{2}-\d{2} \d{1,2}:\d{2})\] ?(.*)$")
+        .expect("The jrnl header pattern is valid.");
+    let tag = Regex::new(r"(?:^|\s)@([\w-]+)").expect("The jrnl tag pattern is valid.");
+
+    let mut entries: Vec<(DateTime<Local>, Vec<&str>)> = vec![];
+    for (i, line) in content.lines().enumerate() {
+        let Some(captures) = header.captures(line) else {
+            if let Some((_, lines)) = entries.last_mut() {
+                lines.push(line);
+            }
+            continue;
+        };
+        let date = NaiveDateTime::parse_from_str(&captures[1], "%Y-%m-%d %H:%M")
+            .ok()
+            .and_then(|date| Local.from_local_datetime(&date).earliest())
+            .ok_or_else(|| DiaryError::BadImport {
+                reason: format!("invalid date `{}` on line {}", &captures[1], i + 1),
+            })?;
+        let title = captures.get(2).map_or("", |title| title.as_str());
+        let title = title.strip_suffix(" *").unwrap_or(title);
+        entries.push((date, vec![title]));
+    }
+
+    Ok(entries
+        .into_iter()
+        .map(|(date, lines)| {
+            let text = lines.join("\n").trim().to_owned();
+            let mut tags: Vec<String> = vec![];
+            for captures in tag.captures_iter(&text) {
+                let name = captures[1].to_owned();
+                if !tags.contains(&name) {
+                    tags.push(name);
+                }
+            }
+            ImportedEntry { date, text, tags }
+        })
+        .filter(|entry| !entry.text.is_empty())
+        .collect())
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::prelude::*;
+
+    use super::parse;
+
+    #[test]
+    fn parse_jrnl() {
+        let content = "[2021-11-06 09:30] Planned the release with @alice. *\n\
+                       Agreed on @work dates.\n\n\
+                       [2021-11-06 18:05] Evening walk.\n\n\
+                       [2021-11-07 10:00]\n";
+        let entries = parse(content).unwrap();
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(
+            entries[0].date,
+            Local.with_ymd_and_hms(2021, 11, 6, 9, 30, 0).unwrap()
+        );
+        assert_eq!(
+            entries[0].text,
+            "Planned the release with @alice.\nAgreed on @work dates."
+        );
+        assert_eq!(entries[0].tags, ["alice", "work"]);
+        assert_eq!(entries[1].text, "Evening walk.");
+        assert!(entries[1].tags.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "value: BadImport")]
+    fn parse_bad_date() {
+        parse("[2021-13-06 09:30] Oops.\n").unwrap();
+    }
+}
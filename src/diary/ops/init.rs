@@ -39,7 +39,7 @@ enum InitStatus {
 /// Either the initialisation status, which provides the path to use, or a DiaryError
 /// if the diary is already initialised somewhere.
 fn establish_path(opts: &InitOptions, potential_path: &Path) -> Result<InitStatus, DiaryError> {
-    if potential_path == PathBuf::from("") {
+    if potential_path == Path::new("") {
         let diary_path = opts.path.join("diary");
         if diary_path.exists() {
             return Err(DiaryError::ExistsHere);
@@ -0,0 +1,119 @@
+//! # List operations
+//!
+//! The list module contains functionality relating to the list command,
+//! independent of the CLI.
+use std::{collections::HashSet, fs, path::PathBuf};
+
+use chrono::NaiveDate;
+
+use crate::{errors::DiaryError, utils::date::DateRange, Diary};
+
+/// The options available to the list command.
+pub struct ListOptions {
+    /// The dates of the entries to list.
+    pub range: DateRange,
+}
+
+/// A summary of a single entry file.
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct EntrySummary {
+    /// The date of the entry.
+    pub date: NaiveDate,
+    /// The name of the entry, or None for the main entry of the day.
+    pub name: Option<String>,
+    /// The path of the entry file.
+    pub path: PathBuf,
+    /// The size of the entry file in bytes.
+    pub size: u64,
+    /// The distinct tags of the entry, from its front matter and tag headings.
+    pub tags: Vec<String>,
+}
+
+/// Lists the entries of the diary within a range of dates.
+///
+/// # Arguments
+///
+/// * `opts` - The options passed by the user at runtime.
+/// * `diary` - Struct representing the diary.
+///
+/// # Returns
+///
+/// A summary of each entry sorted by date, or a DiaryError on any IO issues.
+pub fn list(opts: &ListOptions, diary: &Diary) -> Result<Vec<EntrySummary>, DiaryError> {
+    let mut summaries = vec![];
+    for entry_file in diary.entries()? {
+        if !opts.range.contains(&entry_file.date) {
+            continue;
+        }
+        let entry = diary.read_entry(&entry_file)?;
+        let size = fs::metadata(&entry_file.path)?.len();
+
+        let mut tags: Vec<String> = entry
+            .front_matter
+            .iter()
+            .flat_map(|front_matter| front_matter.tags.iter().map(String::as_str))
+            .chain(entry.tags())
+            .map(str::to_owned)
+            .collect();
+        let mut seen = HashSet::new();
+        tags.retain(|tag| seen.insert(tag.clone()));
+
+        summaries.push(EntrySummary {
+            date: entry_file.date.date_naive(),
+            name: entry_file.name,
+            path: entry_file.path,
+            size,
+            tags,
+        });
+    }
+    Ok(summaries)
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::prelude::*;
+
+    use super::{list, ListOptions};
+    use crate::{ops::testing, utils::date::DateRange, Diary};
+
+    #[test]
+    fn list_entries() {
+        let first_date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+        let second_date = Local.with_ymd_and_hms(2021, 12, 1, 0, 0, 0).unwrap();
+        let config = testing::diary_with_entries(&[(first_date, "work", "Meetings.")]);
+        testing::new_entry(&config, &second_date);
+
+        let diary = Diary::from_config(&config).unwrap();
+
+        let opts = ListOptions {
+            range: DateRange::default(),
+        };
+        let summaries = list(&opts, &diary).unwrap();
+
+        assert_eq!(summaries.len(), 2);
+        assert_eq!(summaries[0].date, first_date.date_naive());
+        assert_eq!(summaries[0].path, diary.get_entry_path(&first_date));
+        assert_eq!(summaries[0].tags, vec!["work"]);
+        assert!(summaries[0].size > 0);
+        assert!(summaries[1].tags.is_empty());
+    }
+
+    #[test]
+    fn list_month() {
+        let config = testing::temp_config();
+        testing::default_init(config.diary_path());
+        let first_date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+        let second_date = Local.with_ymd_and_hms(2021, 12, 1, 0, 0, 0).unwrap();
+        testing::new_entry(&config, &first_date);
+        testing::new_entry(&config, &second_date);
+
+        let diary = Diary::from_config(&config).unwrap();
+        let opts = ListOptions {
+            range: DateRange::month(2021, 12).unwrap(),
+        };
+        let summaries = list(&opts, &diary).unwrap();
+
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(summaries[0].date, second_date.date_naive());
+    }
+}
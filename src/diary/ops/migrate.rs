@@ -0,0 +1,329 @@
+//! # Migrate operations
+//!
+//! The migrate module contains functionality relating to the migrate command,
+//! independent of the CLI.
+use std::{
+    collections::HashSet,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use git2::Repository;
+
+use crate::{
+    errors::DiaryError,
+    utils::{file_system, git, layout::Layout},
+    Diary,
+};
+
+/// The options available to the migrate command.
+pub struct MigrateOptions {
+    /// The prefix the old entries were written with, if it differs from the diary's.
+    pub from_prefix: Option<String>,
+    /// The layout the old entries were written with, if it differs from the diary's.
+    pub from_layout: Option<Layout>,
+    /// Whether to only report the changes rather than make them.
+    pub dry_run: bool,
+}
+
+/// An entry that has been, or would be, moved.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Migration {
+    /// The path of the entry before the move.
+    pub from: PathBuf,
+    /// The path of the entry after the move.
+    pub to: PathBuf,
+}
+
+/// Removes the folders left empty by a move, up to the diary folder.
+fn remove_empty_folders(diary_path: &Path, path: &Path) -> Result<(), DiaryError> {
+    for folder in path.ancestors().skip(1) {
+        if folder == diary_path || !folder.starts_with(diary_path) {
+            break;
+        }
+        if fs::read_dir(folder)?.next().is_some() {
+            break;
+        }
+        fs::remove_dir(folder)?;
+    }
+    Ok(())
+}
+
+/// Finds the entries written under the old prefix and layout that are not where the
+/// diary's prefix and layout would put them.
+///
+/// # Arguments
+///
+/// * `opts` - The options passed by the user at runtime.
+/// * `diary` - Struct representing the diary.
+///
+/// # Returns
+///
+/// The moves needed, or a DiaryError if an entry would end up where a file already
+/// is or where another entry is moved to, or on any IO issues.
+pub fn find_migrations(opts: &MigrateOptions, diary: &Diary) -> Result<Vec<Migration>, DiaryError> {
+    let old_diary = diary.with_layout(
+        opts.from_prefix.as_deref().unwrap_or(diary.prefix()),
+        opts.from_layout
+            .clone()
+            .unwrap_or_else(|| diary.layout().clone()),
+    );
+
+    let mut migrations = vec![];
+    let mut targets = HashSet::new();
+    for entry in old_diary.entries()? {
+        let to = diary.get_named_entry_path(&entry.date, entry.name.as_deref())?;
+        if to == entry.path {
+            continue;
+        }
+        if to.exists() || !targets.insert(to.clone()) {
+            return Err(DiaryError::EntryClash { path: to });
+        }
+        migrations.push(Migration {
+            from: entry.path,
+            to,
+        });
+    }
+    Ok(migrations)
+}
+
+/// Moves every entry written under an old prefix or layout to where the diary
+/// would now put it.
+///
+/// Every move is checked before the first is made. When the diary is a git repo,
+/// moves of tracked entries are staged as `git mv` would.
+///
+/// # Arguments
+///
+/// * `opts` - The options passed by the user at runtime.
+/// * `diary` - Struct representing the diary.
+///
+/// # Returns
+///
+/// The moves made, or that would be made on a dry run. DiaryError, before anything
+/// is moved, if an entry would end up where a file already is or where another entry
+/// is moved to. DiaryError on any other IO issues or on any git issues.
+pub fn migrate(opts: &MigrateOptions, diary: &Diary) -> Result<Vec<Migration>, DiaryError> {
+    let migrations = find_migrations(opts, diary)?;
+    if opts.dry_run {
+        return Ok(migrations);
+    }
+
+    let repo = Repository::open(diary.diary_path()).ok();
+    for migration in &migrations {
+        if let Some(folder) = migration.to.parent() {
+            file_system::create_entry_folder(folder)?;
+        }
+        fs::rename(&migration.from, &migration.to)?;
+        if let Some(repo) = &repo {
+            let from = pathdiff::diff_paths(&migration.from, diary.diary_path()).unwrap();
+            let to = pathdiff::diff_paths(&migration.to, diary.diary_path()).unwrap();
+            git::stage_move(repo, &from, &to)?;
+        }
+        remove_empty_folders(diary.diary_path(), &migration.from)?;
+    }
+    Ok(migrations)
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use chrono::prelude::*;
+    use git2::Repository;
+
+    use super::{migrate, MigrateOptions};
+    use crate::{
+        config::{Config, ConfigBuilder},
+        ops::{init, testing, InitOptions},
+        utils::{
+            git,
+            layout::{Layout, DEFAULT_LAYOUT},
+        },
+        Diary,
+    };
+
+    fn from_prefix(prefix: &str, dry_run: bool) -> MigrateOptions {
+        MigrateOptions {
+            from_prefix: Some(prefix.to_owned()),
+            from_layout: None,
+            dry_run,
+        }
+    }
+
+    fn from_layout(layout: &str) -> MigrateOptions {
+        MigrateOptions {
+            from_prefix: None,
+            from_layout: Some(Layout::parse(layout).unwrap()),
+            dry_run: false,
+        }
+    }
+
+    #[test]
+    fn migrate_prefix() {
+        let config = testing::temp_config();
+        testing::default_init(config.diary_path());
+        let entry_date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+        testing::new_entry(&config, &entry_date);
+
+        let config = ConfigBuilder::from(config).prefix("journal").build();
+        let diary = Diary::from_config(&config).unwrap();
+
+        let migrations = migrate(&from_prefix("diary", false), &diary).unwrap();
+
+        assert_eq!(migrations.len(), 1);
+        assert!(!migrations[0].from.exists());
+        assert_eq!(migrations[0].to, diary.get_entry_path(&entry_date));
+        assert!(migrations[0].to.ends_with("journal_2021-11-06.md"));
+        assert_eq!(diary.entries().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn migrate_layout() {
+        let config = testing::temp_config();
+        testing::default_init(config.diary_path());
+        let entry_date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+        testing::new_entry(&config, &entry_date);
+
+        let config = ConfigBuilder::from(config)
+            .layout("{year}/{month}/{date}.{ext}")
+            .build();
+        let diary = Diary::from_config(&config).unwrap();
+
+        let migrations = migrate(&from_layout(DEFAULT_LAYOUT), &diary).unwrap();
+
+        assert_eq!(migrations.len(), 1);
+        assert!(migrations[0].to.ends_with("2021/11/2021-11-06.md"));
+        assert!(migrations[0].to.exists());
+        assert!(!config.diary_path().join("2021-11").exists());
+    }
+
+    #[test]
+    fn migrate_dry_run() {
+        let config = testing::temp_config();
+        testing::default_init(config.diary_path());
+        let entry_date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+        testing::new_entry(&config, &entry_date);
+
+        let config = ConfigBuilder::from(config).prefix("journal").build();
+        let diary = Diary::from_config(&config).unwrap();
+
+        let migrations = migrate(&from_prefix("diary", true), &diary).unwrap();
+
+        assert_eq!(migrations.len(), 1);
+        assert!(migrations[0].from.exists());
+        assert!(!migrations[0].to.exists());
+    }
+
+    #[test]
+    fn migrate_ignores_other_files() {
+        let config = testing::temp_config();
+        testing::default_init(config.diary_path());
+        let entry_date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+        testing::new_entry(&config, &entry_date);
+        let notes_path = config.diary_path().join("2021-11/notes_2021-11-07.md");
+        fs::write(&notes_path, "Notes.\n").unwrap();
+
+        let config = ConfigBuilder::from(config).prefix("journal").build();
+        let diary = Diary::from_config(&config).unwrap();
+
+        let migrations = migrate(&from_prefix("diary", false), &diary).unwrap();
+
+        assert_eq!(migrations.len(), 1);
+        assert!(notes_path.exists());
+    }
+
+    #[test]
+    fn migrate_from_dateless_layout() {
+        let config = testing::temp_config();
+        let old_config = ConfigBuilder::from(config.clone())
+            .layout("{year}/{month}/{day}.{ext}")
+            .build();
+        testing::default_init(config.diary_path());
+        let entry_date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+        testing::new_entry(&old_config, &entry_date);
+        let old_diary = Diary::from_config(&old_config).unwrap();
+        let named_path = old_diary
+            .get_named_entry_path(&entry_date, Some("evening"))
+            .unwrap();
+        fs::write(&named_path, "Evening.\n").unwrap();
+
+        let diary = Diary::from_config(&config).unwrap();
+        let migrations = migrate(&from_layout("{year}/{month}/{day}.{ext}"), &diary).unwrap();
+
+        assert_eq!(migrations.len(), 2);
+        assert!(diary.get_entry_path(&entry_date).exists());
+        assert!(diary
+            .get_named_entry_path(&entry_date, Some("evening"))
+            .unwrap()
+            .exists());
+        assert!(!config.diary_path().join("2021").exists());
+    }
+
+    #[test]
+    #[should_panic(expected = "value: EntryClash")]
+    fn migrate_clash() {
+        let config = testing::temp_config();
+        testing::default_init(config.diary_path());
+        let entry_date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+        testing::new_entry(&config, &entry_date);
+
+        let config = ConfigBuilder::from(config).prefix("journal").build();
+        testing::new_entry(&config, &entry_date);
+        let diary = Diary::from_config(&config).unwrap();
+
+        migrate(&from_prefix("diary", false), &diary).unwrap();
+    }
+
+    #[test]
+    fn migrate_clash_moves_nothing() {
+        let config = testing::temp_config();
+        testing::default_init(config.diary_path());
+        let dates: Vec<DateTime<Local>> = [5, 6]
+            .into_iter()
+            .map(|day| Local.with_ymd_and_hms(2021, 11, day, 0, 0, 0).unwrap())
+            .collect();
+        for date in &dates {
+            testing::new_entry(&config, date);
+        }
+        let old_diary = Diary::from_config(&config).unwrap();
+
+        let config = ConfigBuilder::from(config).prefix("journal").build();
+        testing::new_entry(&config, &dates[1]);
+        let diary = Diary::from_config(&config).unwrap();
+
+        let result = migrate(&from_prefix("diary", false), &diary);
+
+        assert!(result.is_err());
+        assert!(old_diary.get_entry_path(&dates[0]).exists());
+        assert!(!diary.get_entry_path(&dates[0]).exists());
+    }
+
+    #[test]
+    fn migrate_git_repo() {
+        let config = testing::temp_config();
+        let init_opts = InitOptions {
+            path: testing::temp_path(),
+            prefix: None,
+            git_repo: true,
+        };
+        init(&init_opts, config.diary_path()).unwrap();
+        let entry_date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+        testing::new_entry(&config, &entry_date);
+
+        let repo = Repository::open(config.diary_path()).unwrap();
+        let old_path = std::path::Path::new("2021-11/diary_2021-11-06.md");
+        git::add_and_commit(&repo, old_path, "Add entry").unwrap();
+
+        let config: Config = ConfigBuilder::from(config).prefix("journal").build();
+        let diary = Diary::from_config(&config).unwrap();
+        migrate(&from_prefix("diary", false), &diary).unwrap();
+
+        let mut index = repo.index().unwrap();
+        index.read(true).unwrap();
+        assert!(index.get_path(old_path, 0).is_none());
+        assert!(index
+            .get_path(std::path::Path::new("2021-11/journal_2021-11-06.md"), 0)
+            .is_some());
+    }
+}
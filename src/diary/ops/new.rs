@@ -2,11 +2,15 @@
 //!
 //! The new module contains functionality relating to the new command,
 //! independent of the CLI.
-use std::fs::OpenOptions;
+use std::{
+    fs::{self, OpenOptions},
+    path::{Path, PathBuf},
+};
 
 use chrono::prelude::*;
 
 use crate::{
+    entry::FrontMatter,
     errors::DiaryError,
     utils::{editing, file_system},
     Diary, EntryContent,
@@ -16,6 +20,69 @@ use crate::{
 pub struct NewOptions {
     /// Whether or not to open the new entry for an initial entry.
     pub open: bool,
+    /// The name of a template in the templates folder to use for the entry body.
+    pub template: Option<String>,
+    /// The name of the entry, to keep more than one entry for the day.
+    pub name: Option<String>,
+}
+
+/// Finds a template file in the templates folder by its name, ignoring the extension.
+fn find_template_file(templates_path: &Path, name: &str) -> Option<PathBuf> {
+    let mut files: Vec<PathBuf> = fs::read_dir(templates_path)
+        .ok()?
+        .filter_map(|file| file.ok().map(|f| f.path()))
+        .filter(|path| path.is_file() && path.file_stem().is_some_and(|stem| stem == name))
+        .collect();
+    files.sort();
+    files.into_iter().next()
+}
+
+/// Establishes the template to use for the body of a new entry.
+///
+/// # Arguments
+///
+/// * `opts` - The options passed by the user at runtime.
+/// * `diary` - Struct representing the diary.
+/// * `date` - The date for which to create the new entry.
+///
+/// # Returns
+///
+/// The unexpanded template, if any. A named template takes priority, followed by
+/// a template named after the weekday, then one named `default` and finally the
+/// template in the config. DiaryError if a named template cannot be found.
+fn entry_template(
+    opts: &NewOptions,
+    diary: &Diary,
+    date: &DateTime<Local>,
+) -> Result<Option<String>, DiaryError> {
+    let templates_path = diary.templates_path();
+
+    let template_file = match &opts.template {
+        Some(name) => Some(
+            find_template_file(&templates_path, name)
+                .ok_or_else(|| DiaryError::NoTemplate { name: name.clone() })?,
+        ),
+        None => {
+            let weekday = date.format("%A").to_string().to_lowercase();
+            find_template_file(&templates_path, &weekday)
+                .or_else(|| find_template_file(&templates_path, "default"))
+        }
+    };
+
+    match template_file {
+        Some(path) => Ok(Some(fs::read_to_string(path)?)),
+        None => Ok(diary.template().clone()),
+    }
+}
+
+/// The heading of a new entry, preceded by front matter if it is enabled.
+fn entry_heading(diary: &Diary, date: &DateTime<Local>) -> String {
+    let title = diary.title(date);
+    if !diary.front_matter() {
+        return title;
+    }
+    let front_matter = FrontMatter::new(date, diary.locale().weekday(date));
+    diary.file_type().front_matter(&front_matter) + &title
 }
 
 /// Creates a new diary entry.
@@ -30,7 +97,7 @@ pub struct NewOptions {
 /// # Returns
 ///
 /// The unit upon successful creation of the entry.
-/// DiaryError if the entry already exists.
+/// DiaryError if the entry already exists or the entry name is invalid.
 /// DiaryError on any other IO issues.
 pub fn new(
     opts: &NewOptions,
@@ -38,12 +105,15 @@ pub fn new(
     date: &DateTime<Local>,
     string_getter: editing::StringGetter,
 ) -> Result<(), DiaryError> {
-    let mut new_entry_path = file_system::month_folder(diary.diary_path(), date);
-    file_system::create_month_folder(&new_entry_path)?;
+    let new_entry_path = diary.get_named_entry_path(date, opts.name.as_deref())?;
+    if let Some(entry_folder) = new_entry_path.parent() {
+        file_system::create_entry_folder(entry_folder)?;
+    }
 
-    let entry_name = diary.file_name(date);
+    let template = entry_template(opts, diary, date)?
+        .map(|template| diary.render_template(&template, date))
+        .transpose()?;
 
-    new_entry_path.push(entry_name);
     let result = OpenOptions::new()
         .write(true)
         .create_new(true)
@@ -51,15 +121,17 @@ pub fn new(
 
     let mut file = match result {
         Ok(mut file) => {
-            editing::add_user_content_to_file(&mut file, diary.file_type().title(date))?;
+            editing::add_user_content_to_file(&mut file, entry_heading(diary, date))?;
             file
         }
         Err(e) => return Err(e.into()),
     };
-    if opts.open {
-        let contents = string_getter("".to_owned())?;
-        editing::add_user_content_to_file(&mut file, contents)?;
+    let contents = match (opts.open, template) {
+        (true, template) => string_getter(template.unwrap_or_default())?,
+        (false, Some(template)) => template,
+        (false, None) => return Ok(()),
     };
+    editing::add_user_content_to_file(&mut file, contents)?;
     Ok(())
 }
 
@@ -79,7 +151,11 @@ mod test {
 
         let diary = Diary::from_config(&config).unwrap();
 
-        let new_opts = NewOptions { open: false };
+        let new_opts = NewOptions {
+            open: false,
+            template: None,
+            name: None,
+        };
         let date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
 
         new(&new_opts, &diary, &date, test_string_getter).unwrap();
@@ -96,7 +172,11 @@ mod test {
         let diary = Diary::from_config(&config).unwrap();
 
         let date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
-        let new_opts = NewOptions { open: false };
+        let new_opts = NewOptions {
+            open: false,
+            template: None,
+            name: None,
+        };
 
         new(&new_opts, &diary, &date, test_string_getter).unwrap();
     }
@@ -108,7 +188,11 @@ mod test {
         testing::default_init(config.diary_path());
         let diary = Diary::from_config(&config).unwrap();
 
-        let new_opts = NewOptions { open: false };
+        let new_opts = NewOptions {
+            open: false,
+            template: None,
+            name: None,
+        };
         let date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
 
         new(&new_opts, &diary, &date, test_string_getter).unwrap();
@@ -121,7 +205,11 @@ mod test {
         let config = Config::default();
         let diary = Diary::from_config(&config).unwrap();
 
-        let new_opts = NewOptions { open: false };
+        let new_opts = NewOptions {
+            open: false,
+            template: None,
+            name: None,
+        };
         let date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
 
         new(&new_opts, &diary, &date, test_string_getter).unwrap();
@@ -133,7 +221,11 @@ mod test {
         testing::default_init(config.diary_path());
         let diary = Diary::from_config(&config).unwrap();
 
-        let new_opts = NewOptions { open: true };
+        let new_opts = NewOptions {
+            open: true,
+            template: None,
+            name: None,
+        };
         let date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
 
         new(&new_opts, &diary, &date, test_string_getter).unwrap();
@@ -145,4 +237,232 @@ mod test {
         let content = fs::read_to_string(test_path).unwrap();
         assert!(content.contains("Test content"));
     }
+
+    #[test]
+    fn new_config_template() {
+        let diary_dir = testing::temp_diary_path();
+        let config = Config::builder()
+            .diary_path(diary_dir)
+            .template("Goals for week {week}:\n")
+            .build();
+        testing::default_init(config.diary_path());
+        let diary = Diary::from_config(&config).unwrap();
+
+        let new_opts = NewOptions {
+            open: false,
+            template: None,
+            name: None,
+        };
+        let date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+
+        new(&new_opts, &diary, &date, test_string_getter).unwrap();
+
+        let content = fs::read_to_string(diary.get_entry_path(&date)).unwrap();
+        assert!(content.ends_with("November 2021\n\nGoals for week 44:\n"));
+    }
+
+    #[test]
+    fn new_weekday_template() {
+        let config = testing::temp_config();
+        testing::default_init(config.diary_path());
+        let diary = Diary::from_config(&config).unwrap();
+
+        let templates_path = diary.templates_path();
+        fs::create_dir(&templates_path).unwrap();
+        fs::write(templates_path.join("default.md"), "Default\n").unwrap();
+        fs::write(templates_path.join("saturday.md"), "Weekend {iso_date}\n").unwrap();
+
+        let new_opts = NewOptions {
+            open: true,
+            template: None,
+            name: None,
+        };
+        let date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+
+        new(&new_opts, &diary, &date, test_string_getter).unwrap();
+
+        let content = fs::read_to_string(diary.get_entry_path(&date)).unwrap();
+        assert!(content.contains("Weekend 2021-11-06\nTest content"));
+        assert!(!content.contains("Default"));
+    }
+
+    #[test]
+    fn new_named_template() {
+        let config = testing::temp_config();
+        testing::default_init(config.diary_path());
+        let diary = Diary::from_config(&config).unwrap();
+
+        let templates_path = diary.templates_path();
+        fs::create_dir(&templates_path).unwrap();
+        fs::write(templates_path.join("saturday.md"), "Weekend\n").unwrap();
+        fs::write(templates_path.join("retro.md"), "## Went well\n").unwrap();
+
+        let new_opts = NewOptions {
+            open: false,
+            template: Some("retro".to_owned()),
+            name: None,
+        };
+        let date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+
+        new(&new_opts, &diary, &date, test_string_getter).unwrap();
+
+        let content = fs::read_to_string(diary.get_entry_path(&date)).unwrap();
+        assert!(content.contains("## Went well"));
+        assert!(!content.contains("Weekend"));
+    }
+
+    #[test]
+    #[should_panic(expected = "value: NoTemplate")]
+    fn new_missing_named_template() {
+        let config = testing::temp_config();
+        testing::default_init(config.diary_path());
+        let diary = Diary::from_config(&config).unwrap();
+
+        let new_opts = NewOptions {
+            open: false,
+            template: Some("retro".to_owned()),
+            name: None,
+        };
+        let date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+
+        new(&new_opts, &diary, &date, test_string_getter).unwrap();
+    }
+
+    #[test]
+    fn new_template_escaped_braces() {
+        let config = testing::temp_config();
+        testing::default_init(config.diary_path());
+        let diary = Diary::from_config(&config).unwrap();
+
+        let templates_path = diary.templates_path();
+        fs::create_dir(&templates_path).unwrap();
+        fs::write(
+            templates_path.join("default.md"),
+            "```json\n{{\"date\": \"{iso_date}\"}}\n```\n",
+        )
+        .unwrap();
+
+        let new_opts = NewOptions {
+            open: false,
+            template: None,
+            name: None,
+        };
+        let date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+
+        new(&new_opts, &diary, &date, test_string_getter).unwrap();
+
+        let content = fs::read_to_string(diary.get_entry_path(&date)).unwrap();
+        assert!(content.contains("{\"date\": \"2021-11-06\"}\n"));
+    }
+
+    #[test]
+    #[should_panic(expected = "write `{{` for a literal brace")]
+    fn new_template_literal_brace() {
+        let config = testing::temp_config();
+        testing::default_init(config.diary_path());
+        let diary = Diary::from_config(&config).unwrap();
+
+        let templates_path = diary.templates_path();
+        fs::create_dir(&templates_path).unwrap();
+        fs::write(templates_path.join("default.md"), "fn main() {\n").unwrap();
+
+        let new_opts = NewOptions {
+            open: false,
+            template: None,
+            name: None,
+        };
+        let date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+
+        new(&new_opts, &diary, &date, test_string_getter).unwrap();
+    }
+
+    #[test]
+    fn new_front_matter() {
+        let diary_dir = testing::temp_diary_path();
+        let config = Config::builder()
+            .diary_path(diary_dir)
+            .front_matter(true)
+            .build();
+        testing::default_init(config.diary_path());
+        let diary = Diary::from_config(&config).unwrap();
+
+        let new_opts = NewOptions {
+            open: false,
+            template: None,
+            name: None,
+        };
+        let date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+
+        new(&new_opts, &diary, &date, test_string_getter).unwrap();
+
+        let content = fs::read_to_string(diary.get_entry_path(&date)).unwrap();
+        assert_eq!(
+            content,
+            "---\ndate: 2021-11-06\nweekday: Saturday\nwords: 0\ntags: []\n---\n\n# Saturday 6<sup>th</sup> November 2021\n\n"
+        );
+    }
+
+    #[test]
+    fn new_rst_front_matter() {
+        let diary_dir = testing::temp_diary_path();
+        let config = Config::builder()
+            .diary_path(diary_dir)
+            .file_type("rst")
+            .front_matter(true)
+            .build();
+        testing::default_init(config.diary_path());
+        let diary = Diary::from_config(&config).unwrap();
+
+        let new_opts = NewOptions {
+            open: false,
+            template: None,
+            name: None,
+        };
+        let date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+
+        new(&new_opts, &diary, &date, test_string_getter).unwrap();
+
+        let content = fs::read_to_string(diary.get_entry_path(&date)).unwrap();
+        assert!(content
+            .starts_with(":date: 2021-11-06\n:weekday: Saturday\n:words: 0\n:tags:\n\nSaturday"));
+    }
+
+    #[test]
+    fn new_named_entry() {
+        let config = testing::temp_config();
+        testing::default_init(config.diary_path());
+        let diary = Diary::from_config(&config).unwrap();
+        let date = Local.with_ymd_and_hms(2022, 1, 13, 0, 0, 0).unwrap();
+
+        let mut new_opts = NewOptions {
+            open: false,
+            template: None,
+            name: None,
+        };
+        new(&new_opts, &diary, &date, test_string_getter).unwrap();
+        new_opts.name = Some("evening".to_owned());
+        new(&new_opts, &diary, &date, test_string_getter).unwrap();
+
+        let entry_path = diary.get_named_entry_path(&date, Some("evening")).unwrap();
+
+        assert!(entry_path.ends_with("2022-01/diary_2022-01-13_evening.md"));
+        assert!(entry_path.exists());
+        assert!(diary.get_entry_path(&date).exists());
+    }
+
+    #[test]
+    #[should_panic(expected = "value: BadEntryName")]
+    fn new_bad_entry_name() {
+        let config = testing::temp_config();
+        testing::default_init(config.diary_path());
+        let diary = Diary::from_config(&config).unwrap();
+        let date = Local.with_ymd_and_hms(2022, 1, 13, 0, 0, 0).unwrap();
+
+        let new_opts = NewOptions {
+            open: false,
+            template: None,
+            name: Some("late/evening".to_owned()),
+        };
+        new(&new_opts, &diary, &date, test_string_getter).unwrap();
+    }
 }
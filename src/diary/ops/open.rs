@@ -13,6 +13,8 @@ use crate::{errors::DiaryError, Diary};
 pub struct OpenFileOptions {
     /// The date of the entry to open.
     pub entry_date: DateTime<Local>,
+    /// The name of the entry to open, or None for the main entry.
+    pub name: Option<String>,
 }
 
 /// Opens a specific diary entry for editing.
@@ -22,13 +24,13 @@ pub struct OpenFileOptions {
 /// * `opts` - The options passed by the user at runtime.
 /// * `diary` - Struct representing the diary.
 /// * `user_input` - A function of type UserInput. A function that takes a file
-/// and adds content to it.
+///   and adds content to it.
 pub fn open(
     opts: &OpenFileOptions,
     diary: &Diary,
     user_input: UserInput,
 ) -> Result<(), DiaryError> {
-    let entry_path = diary.get_entry_path(&opts.entry_date);
+    let entry_path = diary.get_named_entry_path(&opts.entry_date, opts.name.as_deref())?;
 
     if !entry_path.exists() {
         return Err(DiaryError::NoEntry { source: None });
@@ -40,7 +42,7 @@ pub fn open(
     }
 }
 
-type UserInput = fn(P: PathBuf) -> io::Result<()>;
+type UserInput = fn(p: PathBuf) -> io::Result<()>;
 
 #[cfg(test)]
 mod test {
@@ -77,12 +79,19 @@ mod test {
         testing::default_init(config.diary_path());
         let diary = Diary::from_config(&config).unwrap();
 
-        let new_opts = NewOptions { open: false };
+        let new_opts = NewOptions {
+            open: false,
+            template: None,
+            name: None,
+        };
         let entry_date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
 
         new(&new_opts, &diary, &entry_date, test_string_getter).unwrap();
 
-        let opts = OpenFileOptions { entry_date };
+        let opts = OpenFileOptions {
+            entry_date,
+            name: None,
+        };
         open(&opts, &diary, test_user_input).unwrap();
 
         let entry_path = diary.get_entry_path(&entry_date);
@@ -100,7 +109,10 @@ mod test {
         let diary = Diary::from_config(&config).unwrap();
 
         let entry_date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
-        let opts = OpenFileOptions { entry_date };
+        let opts = OpenFileOptions {
+            entry_date,
+            name: None,
+        };
 
         open(&opts, &diary, test_user_input).unwrap();
     }
@@ -112,7 +124,10 @@ mod test {
         let diary = Diary::from_config(&config).unwrap();
 
         let entry_date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
-        let opts = OpenFileOptions { entry_date };
+        let opts = OpenFileOptions {
+            entry_date,
+            name: None,
+        };
 
         open(&opts, &diary, test_user_input).unwrap();
     }
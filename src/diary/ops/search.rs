@@ -0,0 +1,266 @@
+//! # Search operations
+//!
+//! The search module contains functionality relating to the search command,
+//! independent of the CLI.
+use std::{fs, path::PathBuf};
+
+use chrono::{DateTime, Local, NaiveDate};
+use regex::{Regex, RegexBuilder};
+
+use crate::{errors::DiaryError, read_timestamp, Diary, EntryContent};
+
+/// The options available to the search command.
+pub struct SearchOptions {
+    /// The text or regular expression to search for.
+    pub pattern: String,
+    /// Whether the pattern is a regular expression rather than plain text.
+    pub regex: bool,
+    /// Whether to ignore case when matching.
+    pub ignore_case: bool,
+    /// Only match lines in sections with this tag.
+    pub tag: Option<String>,
+    /// The number of lines to show before and after each match.
+    pub context: usize,
+}
+
+/// A line of an entry matching the search pattern.
+#[derive(Debug, PartialEq, Eq)]
+pub struct SearchMatch {
+    /// The date of the entry.
+    pub date: NaiveDate,
+    /// The name of the entry, or None for the main entry of the day.
+    pub name: Option<String>,
+    /// The path of the entry file.
+    pub path: PathBuf,
+    /// The tag of the section enclosing the line, or None before the first tag.
+    pub section: Option<String>,
+    /// The number of the line in the entry file, starting from 1.
+    pub line_number: usize,
+    /// The matching line.
+    pub line: String,
+    /// The lines before the match, up to the requested context.
+    pub before: Vec<String>,
+    /// The lines after the match, up to the requested context.
+    pub after: Vec<String>,
+}
+
+/// Compiles the search pattern, escaping it unless it is a regular expression.
+fn search_regex(opts: &SearchOptions) -> Result<Regex, DiaryError> {
+    let pattern = if opts.regex {
+        opts.pattern.clone()
+    } else {
+        regex::escape(&opts.pattern)
+    };
+    RegexBuilder::new(&pattern)
+        .case_insensitive(opts.ignore_case)
+        .build()
+        .map_err(|e| DiaryError::BadPattern {
+            pattern: opts.pattern.clone(),
+            reason: e.to_string(),
+        })
+}
+
+/// Finds the tag of the section enclosing each line of an entry, skipping the
+/// front matter and the lines of headings after their first.
+///
+/// # Returns
+///
+/// The index and enclosing tag of each line that can be matched, with the tag read
+/// back through the tag format.
+fn line_sections(
+    diary: &Diary,
+    date: &DateTime<Local>,
+    lines: &[&str],
+) -> Vec<(usize, Option<String>)> {
+    let file_type = diary.file_type();
+    let mut i = file_type
+        .read_front_matter(lines)
+        .map_or(0, |(_, span)| span);
+    let mut section = None;
+    let mut sections = vec![];
+
+    while i < lines.len() {
+        let heading = file_type.read_heading(&lines[i..]);
+        let span = heading.as_ref().map_or(1, |(_, _, span)| *span);
+        match heading {
+            Some((_, 1, _)) => section = None,
+            Some((text, 2, _)) => section = Some(diary.tag_name(&text, date)),
+            Some((text, 3, _)) => {
+                if let Some((_, tag)) = read_timestamp(&text) {
+                    section = Some(diary.tag_name(tag, date)).filter(|tag| !tag.is_empty());
+                }
+            }
+            _ => {}
+        }
+        sections.push((i, section.clone()));
+        i += span;
+    }
+    sections
+}
+
+/// Searches every entry of the diary for lines matching a pattern.
+///
+/// # Arguments
+///
+/// * `opts` - The options passed by the user at runtime.
+/// * `diary` - Struct representing the diary.
+///
+/// # Returns
+///
+/// The matches sorted by date and line, or a DiaryError if the pattern is not a
+/// valid regular expression or on any IO issues.
+pub fn search(opts: &SearchOptions, diary: &Diary) -> Result<Vec<SearchMatch>, DiaryError> {
+    let regex = search_regex(opts)?;
+
+    let mut matches = vec![];
+    for entry_file in diary.entries()? {
+        let content = fs::read_to_string(&entry_file.path)?;
+        let lines: Vec<&str> = content.lines().collect();
+
+        for (i, section) in line_sections(diary, &entry_file.date, &lines) {
+            if opts.tag.is_some() && section != opts.tag {
+                continue;
+            }
+            if !regex.is_match(lines[i]) {
+                continue;
+            }
+            let to_owned = |lines: &[&str]| lines.iter().map(|line| (*line).to_owned()).collect();
+            matches.push(SearchMatch {
+                date: entry_file.date.date_naive(),
+                name: entry_file.name.clone(),
+                path: entry_file.path.clone(),
+                section,
+                line_number: i + 1,
+                line: lines[i].to_owned(),
+                before: to_owned(&lines[i.saturating_sub(opts.context)..i]),
+                after: to_owned(&lines[i + 1..(i + 1 + opts.context).min(lines.len())]),
+            });
+        }
+    }
+    Ok(matches)
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::prelude::*;
+
+    use super::{search, SearchOptions};
+    use crate::{config::Config, ops::testing, Diary};
+
+    fn opts(pattern: &str) -> SearchOptions {
+        SearchOptions {
+            pattern: pattern.to_owned(),
+            regex: false,
+            ignore_case: false,
+            tag: None,
+            context: 1,
+        }
+    }
+
+    #[test]
+    fn search_tag_with_tag_format() {
+        let config = Config::builder()
+            .diary_path(testing::temp_diary_path())
+            .tag_format("{tag} ({day} {month})")
+            .build();
+        testing::default_init(config.diary_path());
+        let date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+        testing::add_entries(
+            &config,
+            &[
+                (date, "work", "Fixed the build."),
+                (date, "home", "Built a shelf."),
+            ],
+        );
+        let diary = Diary::from_config(&config).unwrap();
+
+        let opts = SearchOptions {
+            tag: Some("home".to_owned()),
+            ..opts("Built")
+        };
+        let matches = search(&opts, &diary).unwrap();
+
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].section.as_deref(), Some("home"));
+    }
+
+    #[test]
+    fn search_plain() {
+        let first_date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+        let second_date = Local.with_ymd_and_hms(2021, 11, 7, 0, 0, 0).unwrap();
+        let config = testing::diary_with_entries(&[
+            (first_date, "work", "Fixed the build."),
+            (first_date, "home", "Built a shelf."),
+            (second_date, "work", "Reviewed the Build pipeline."),
+        ]);
+        let diary = Diary::from_config(&config).unwrap();
+
+        let matches = search(&opts("build"), &diary).unwrap();
+
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].line, "Fixed the build.");
+        assert_eq!(matches[0].section.as_deref(), Some("work"));
+        assert_eq!(matches[0].before, vec![""]);
+        assert_eq!(matches[0].after, vec!["## home"]);
+    }
+
+    #[test]
+    fn search_ignore_case() {
+        let first_date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+        let second_date = Local.with_ymd_and_hms(2021, 11, 7, 0, 0, 0).unwrap();
+        let config = testing::diary_with_entries(&[
+            (first_date, "work", "Fixed the build."),
+            (first_date, "home", "Built a shelf."),
+            (second_date, "work", "Reviewed the Build pipeline."),
+        ]);
+        let diary = Diary::from_config(&config).unwrap();
+
+        let opts = SearchOptions {
+            ignore_case: true,
+            ..opts("build")
+        };
+        let matches = search(&opts, &diary).unwrap();
+
+        assert_eq!(matches.len(), 2);
+        assert_eq!(
+            matches[1].date,
+            NaiveDate::from_ymd_opt(2021, 11, 7).unwrap()
+        );
+    }
+
+    #[test]
+    fn search_regex_tag() {
+        let first_date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+        let second_date = Local.with_ymd_and_hms(2021, 11, 7, 0, 0, 0).unwrap();
+        let config = testing::diary_with_entries(&[
+            (first_date, "work", "Fixed the build."),
+            (first_date, "home", "Built a shelf."),
+            (second_date, "work", "Reviewed the Build pipeline."),
+        ]);
+        let diary = Diary::from_config(&config).unwrap();
+
+        let opts = SearchOptions {
+            regex: true,
+            tag: Some("home".to_owned()),
+            ..opts(r"Buil[dt]")
+        };
+        let matches = search(&opts, &diary).unwrap();
+
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].line, "Built a shelf.");
+    }
+
+    #[test]
+    #[should_panic(expected = "value: BadPattern")]
+    fn search_bad_regex() {
+        let date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+        let config = testing::diary_with_entries(&[(date, "work", "Fixed the build.")]);
+        let diary = Diary::from_config(&config).unwrap();
+
+        let opts = SearchOptions {
+            regex: true,
+            ..opts("(")
+        };
+        search(&opts, &diary).unwrap();
+    }
+}
@@ -0,0 +1,158 @@
+//! # Show operations
+//!
+//! The show module contains functionality relating to the show command,
+//! independent of the CLI.
+use std::fs;
+
+use crate::{
+    entry::Entry, errors::DiaryError, underline, utils::date::DateRange, Diary, EntryContent,
+};
+
+/// Which entries to show.
+pub enum EntrySelection {
+    /// The entries within a range of dates.
+    Range(DateRange),
+    /// The given number of most recent entries.
+    Last(usize),
+}
+
+/// The options available to the show command.
+pub struct ShowOptions {
+    /// The entries to show.
+    pub selection: EntrySelection,
+    /// Whether to show the entry files as they are rather than as plain text.
+    pub raw: bool,
+}
+
+/// Renders an entry as plain text for reading in a terminal, leaving out the front
+/// matter and heading markup.
+///
+/// # Arguments
+///
+/// * `file_type` - The file type the entry was read from.
+/// * `entry` - The entry to render.
+pub fn render_plain(file_type: &impl EntryContent, entry: &Entry) -> String {
+    let title = file_type.strip_superscript(&entry.title);
+    let mut content = underline(&title, '=');
+    if !entry.preamble.is_empty() {
+        content.push_str(&entry.preamble);
+        content.push_str("\n\n");
+    }
+    for section in &entry.sections {
+        let heading = match (section.time, section.tag.as_str()) {
+            (Some(time), "") => time.format("%H:%M").to_string(),
+            (Some(time), tag) => format!("{} — {}", time.format("%H:%M"), tag),
+            (None, tag) => tag.to_owned(),
+        };
+        content.push_str(&underline(&heading, '-'));
+        if !section.body.is_empty() {
+            content.push_str(&section.body);
+            content.push_str("\n\n");
+        }
+    }
+    content.trim_end().to_owned() + "\n"
+}
+
+/// Reads the selected entries of the diary for printing.
+///
+/// # Arguments
+///
+/// * `opts` - The options passed by the user at runtime.
+/// * `diary` - Struct representing the diary.
+///
+/// # Returns
+///
+/// The entries oldest first, separated by a blank line, or a DiaryError if no
+/// entries are selected or on any IO issues.
+pub fn show(opts: &ShowOptions, diary: &Diary) -> Result<String, DiaryError> {
+    let mut entry_files = diary.entries()?;
+    match opts.selection {
+        EntrySelection::Range(range) => {
+            entry_files.retain(|entry_file| range.contains(&entry_file.date))
+        }
+        EntrySelection::Last(count) => {
+            entry_files.drain(..entry_files.len().saturating_sub(count));
+        }
+    }
+    if entry_files.is_empty() {
+        return Err(DiaryError::NoEntry { source: None });
+    }
+
+    let mut documents = vec![];
+    for entry_file in &entry_files {
+        let document = if opts.raw {
+            fs::read_to_string(&entry_file.path)?
+        } else {
+            render_plain(diary.file_type(), &diary.read_entry(entry_file)?)
+        };
+        documents.push(document);
+    }
+    Ok(documents.join("\n"))
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use chrono::prelude::*;
+
+    use super::{show, EntrySelection, ShowOptions};
+    use crate::{ops::testing, utils::date::DateRange, Diary};
+
+    #[test]
+    fn show_rendered() {
+        let entry_date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+        let config = testing::diary_with_entries(&[(entry_date, "work", "Meetings.")]);
+
+        let diary = Diary::from_config(&config).unwrap();
+
+        let opts = ShowOptions {
+            selection: EntrySelection::Range(DateRange::day(entry_date.date_naive())),
+            raw: false,
+        };
+        let content = show(&opts, &diary).unwrap();
+
+        assert_eq!(
+            content,
+            "Saturday 6th November 2021\n==========================\n\n\
+             work\n----\n\nMeetings.\n"
+        );
+    }
+
+    #[test]
+    fn show_raw_last() {
+        let config = testing::temp_config();
+        testing::default_init(config.diary_path());
+        for day in 5..=7 {
+            let entry_date = Local.with_ymd_and_hms(2021, 11, day, 0, 0, 0).unwrap();
+            testing::new_entry(&config, &entry_date);
+        }
+
+        let diary = Diary::from_config(&config).unwrap();
+        let opts = ShowOptions {
+            selection: EntrySelection::Last(2),
+            raw: true,
+        };
+        let content = show(&opts, &diary).unwrap();
+
+        let first_date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+        let first_entry = fs::read_to_string(diary.get_entry_path(&first_date)).unwrap();
+        assert!(content.starts_with(&first_entry));
+        assert!(content.contains("Sunday 7<sup>th</sup> November 2021"));
+        assert!(!content.contains("Friday 5<sup>th</sup>"));
+    }
+
+    #[test]
+    #[should_panic(expected = "value: NoEntry")]
+    fn show_no_entry() {
+        let config = testing::temp_config();
+        testing::default_init(config.diary_path());
+
+        let diary = Diary::from_config(&config).unwrap();
+        let opts = ShowOptions {
+            selection: EntrySelection::Last(1),
+            raw: false,
+        };
+        show(&opts, &diary).unwrap();
+    }
+}
@@ -0,0 +1,282 @@
+//! # Stats operations
+//!
+//! The stats module contains functionality relating to the stats command,
+//! independent of the CLI.
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, BTreeSet, HashMap},
+};
+
+use chrono::prelude::*;
+
+use crate::{errors::DiaryError, utils::date::DateRange, Diary};
+
+/// The options available to the stats command.
+pub struct StatsOptions {
+    /// The dates of the entries to count.
+    pub range: DateRange,
+    /// The number of most-used tags to report.
+    pub top_tags: usize,
+}
+
+/// The entries and words written in a month or year.
+#[derive(Debug, Serialize, PartialEq)]
+pub struct PeriodStats {
+    /// The month, e.g. `2021-11`, or year, e.g. `2021`.
+    pub period: String,
+    /// The number of entries.
+    pub entries: usize,
+    /// The number of words.
+    pub words: usize,
+    /// The average number of words per entry.
+    pub words_per_entry: f64,
+}
+
+/// The number of entries written on a day of the week.
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct WeekdayStats {
+    /// The day of the week, e.g. `Monday`.
+    pub weekday: String,
+    /// The number of entries.
+    pub entries: usize,
+}
+
+/// The number of sections written under a tag.
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct TagStats {
+    /// The name of the tag.
+    pub tag: String,
+    /// The number of sections.
+    pub sections: usize,
+}
+
+/// Writing statistics of a diary.
+#[derive(Debug, Serialize, PartialEq)]
+pub struct Stats {
+    /// The number of entries.
+    pub entries: usize,
+    /// The number of words across all entries.
+    pub words: usize,
+    /// The average number of words per entry.
+    pub words_per_entry: f64,
+    /// The entries and words of each month, oldest first.
+    pub months: Vec<PeriodStats>,
+    /// The entries and words of each year, oldest first.
+    pub years: Vec<PeriodStats>,
+    /// The number of consecutive days with an entry up to today, or up to
+    /// yesterday when today's entry has not been written yet.
+    pub current_streak: usize,
+    /// The largest number of consecutive days with an entry.
+    pub longest_streak: usize,
+    /// The entries written on each day of the week, busiest first.
+    pub weekdays: Vec<WeekdayStats>,
+    /// The most-used tags, most used first.
+    pub top_tags: Vec<TagStats>,
+}
+
+/// The average of a total over a count, or 0 when the count is 0.
+fn average(total: usize, count: usize) -> f64 {
+    if count == 0 {
+        return 0.0;
+    }
+    total as f64 / count as f64
+}
+
+/// Sums the entries and words of each period.
+fn period_stats(totals: BTreeMap<String, (usize, usize)>) -> Vec<PeriodStats> {
+    totals
+        .into_iter()
+        .map(|(period, (entries, words))| PeriodStats {
+            period,
+            entries,
+            words,
+            words_per_entry: average(words, entries),
+        })
+        .collect()
+}
+
+/// Finds the current and longest runs of consecutive days in a set of dates.
+///
+/// # Arguments
+///
+/// * `dates` - The days with an entry.
+/// * `today` - The day the current streak runs up to.
+///
+/// # Returns
+///
+/// The current and longest streaks, in days.
+fn streaks(dates: &BTreeSet<NaiveDate>, today: NaiveDate) -> (usize, usize) {
+    let mut longest = 0;
+    let mut run = 0;
+    let mut previous: Option<NaiveDate> = None;
+    for date in dates {
+        run = match previous {
+            Some(previous) if previous.succ_opt() == Some(*date) => run + 1,
+            _ => 1,
+        };
+        longest = longest.max(run);
+        previous = Some(*date);
+    }
+
+    let mut day = if dates.contains(&today) {
+        Some(today)
+    } else {
+        today.pred_opt()
+    };
+    let mut current = 0;
+    while let Some(date) = day.filter(|date| dates.contains(date)) {
+        current += 1;
+        day = date.pred_opt();
+    }
+    (current, longest)
+}
+
+/// Calculates the writing statistics of the diary.
+///
+/// # Arguments
+///
+/// * `opts` - The options passed by the user at runtime.
+/// * `diary` - Struct representing the diary.
+/// * `today` - The day the current streak runs up to, usually today.
+///
+/// # Returns
+///
+/// The statistics, or a DiaryError on any IO issues.
+pub fn stats(opts: &StatsOptions, diary: &Diary, today: NaiveDate) -> Result<Stats, DiaryError> {
+    let mut entries = 0;
+    let mut words = 0;
+    let mut months: BTreeMap<String, (usize, usize)> = BTreeMap::new();
+    let mut years: BTreeMap<String, (usize, usize)> = BTreeMap::new();
+    let mut dates = BTreeSet::new();
+    let mut weekdays: HashMap<Weekday, (String, usize)> = HashMap::new();
+    let mut tags: HashMap<String, usize> = HashMap::new();
+
+    for entry_file in diary.entries()? {
+        if !opts.range.contains(&entry_file.date) {
+            continue;
+        }
+        let entry = diary.read_entry(&entry_file)?;
+        let date = entry.date.date_naive();
+        let entry_words = entry.word_count();
+
+        entries += 1;
+        words += entry_words;
+        for (totals, period) in [
+            (&mut months, date.format("%Y-%m").to_string()),
+            (&mut years, date.format("%Y").to_string()),
+        ] {
+            let total = totals.entry(period).or_default();
+            total.0 += 1;
+            total.1 += entry_words;
+        }
+        dates.insert(date);
+        weekdays
+            .entry(date.weekday())
+            .or_insert_with(|| (date.format("%A").to_string(), 0))
+            .1 += 1;
+        for tag in entry.sections.iter().map(|section| &section.tag) {
+            if !tag.is_empty() {
+                *tags.entry(tag.clone()).or_default() += 1;
+            }
+        }
+    }
+
+    let (current_streak, longest_streak) = streaks(&dates, today);
+
+    let mut weekdays: Vec<(Weekday, (String, usize))> = weekdays.into_iter().collect();
+    weekdays.sort_by_key(|(weekday, (_, count))| (Reverse(*count), weekday.num_days_from_monday()));
+    let weekdays = weekdays
+        .into_iter()
+        .map(|(_, (weekday, entries))| WeekdayStats { weekday, entries })
+        .collect();
+
+    let mut tags: Vec<(String, usize)> = tags.into_iter().collect();
+    tags.sort_by(|(a_tag, a_count), (b_tag, b_count)| b_count.cmp(a_count).then(a_tag.cmp(b_tag)));
+    let top_tags = tags
+        .into_iter()
+        .take(opts.top_tags)
+        .map(|(tag, sections)| TagStats { tag, sections })
+        .collect();
+
+    Ok(Stats {
+        entries,
+        words,
+        words_per_entry: average(words, entries),
+        months: period_stats(months),
+        years: period_stats(years),
+        current_streak,
+        longest_streak,
+        weekdays,
+        top_tags,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeSet;
+
+    use chrono::prelude::*;
+
+    use super::{stats, streaks, StatsOptions};
+    use crate::{ops::testing, utils::date::DateRange, Diary};
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2021, month, day).unwrap()
+    }
+
+    #[test]
+    fn find_streaks() {
+        let dates = BTreeSet::from([
+            date(11, 1),
+            date(11, 2),
+            date(11, 3),
+            date(11, 6),
+            date(11, 7),
+        ]);
+
+        assert_eq!(streaks(&dates, date(11, 7)), (2, 3));
+        assert_eq!(streaks(&dates, date(11, 8)), (2, 3));
+        assert_eq!(streaks(&dates, date(11, 9)), (0, 3));
+        assert_eq!(streaks(&BTreeSet::new(), date(11, 9)), (0, 0));
+    }
+
+    #[test]
+    fn diary_stats() {
+        let config = testing::diary_with_entries(&[
+            (
+                Local.with_ymd_and_hms(2021, 11, 5, 0, 0, 0).unwrap(),
+                "work",
+                "Wrote the report.",
+            ),
+            (
+                Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap(),
+                "home",
+                "Built a shelf.",
+            ),
+            (
+                Local.with_ymd_and_hms(2021, 11, 7, 0, 0, 0).unwrap(),
+                "work",
+                "Reviewed it.",
+            ),
+        ]);
+        let diary = Diary::from_config(&config).unwrap();
+
+        let opts = StatsOptions {
+            range: DateRange::default(),
+            top_tags: 1,
+        };
+        let stats = stats(&opts, &diary, date(11, 7)).unwrap();
+
+        assert_eq!(stats.entries, 3);
+        assert_eq!(stats.words, 8);
+        assert_eq!(stats.months.len(), 1);
+        assert_eq!(stats.months[0].period, "2021-11");
+        assert_eq!(stats.years[0].words, 8);
+        assert_eq!(stats.current_streak, 3);
+        assert_eq!(stats.longest_streak, 3);
+        assert_eq!(stats.weekdays[0].weekday, "Friday");
+        assert_eq!(stats.top_tags.len(), 1);
+        assert_eq!(stats.top_tags[0].tag, "work");
+        assert_eq!(stats.top_tags[0].sections, 2);
+    }
+}
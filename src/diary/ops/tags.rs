@@ -0,0 +1,241 @@
+//! # Tag operations
+//!
+//! The tags module contains functionality relating to the tags and tag commands,
+//! independent of the CLI.
+use std::collections::BTreeMap;
+
+use chrono::NaiveDate;
+
+use crate::{errors::DiaryError, utils::date::DateRange, Diary, EntryContent};
+
+/// How often a tag has been used.
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct TagSummary {
+    /// The name of the tag.
+    pub tag: String,
+    /// The number of sections written under the tag.
+    pub count: usize,
+    /// The date of the first entry using the tag.
+    pub first: NaiveDate,
+    /// The date of the last entry using the tag.
+    pub last: NaiveDate,
+}
+
+/// The options available to the tag command.
+pub struct TagOptions {
+    /// The tag to collect the sections of.
+    pub tag: String,
+    /// The dates of the entries to collect sections from.
+    pub range: DateRange,
+}
+
+/// Counts the sections written under each tag of the diary.
+///
+/// # Arguments
+///
+/// * `diary` - Struct representing the diary.
+///
+/// # Returns
+///
+/// A summary of each tag sorted by name, or a DiaryError on any IO issues.
+pub fn tags(diary: &Diary) -> Result<Vec<TagSummary>, DiaryError> {
+    let mut summaries: BTreeMap<String, TagSummary> = BTreeMap::new();
+    for entry_file in diary.entries()? {
+        let entry = diary.read_entry(&entry_file)?;
+        let date = entry.date.date_naive();
+        for section in entry
+            .sections
+            .iter()
+            .filter(|section| !section.tag.is_empty())
+        {
+            let summary = summaries
+                .entry(section.tag.clone())
+                .or_insert_with(|| TagSummary {
+                    tag: section.tag.clone(),
+                    count: 0,
+                    first: date,
+                    last: date,
+                });
+            summary.count += 1;
+            summary.first = summary.first.min(date);
+            summary.last = summary.last.max(date);
+        }
+    }
+    Ok(summaries.into_values().collect())
+}
+
+/// Collects every section written under a tag into one document of the diary's
+/// file type, with a heading for each section giving the title of its entry.
+///
+/// # Arguments
+///
+/// * `opts` - The options passed by the user at runtime.
+/// * `diary` - Struct representing the diary.
+///
+/// # Returns
+///
+/// The document, or a DiaryError if no section in the range has the tag or on
+/// any IO issues.
+pub fn tag(opts: &TagOptions, diary: &Diary) -> Result<String, DiaryError> {
+    let file_type = diary.file_type();
+    let mut content = file_type.heading(&opts.tag, 1);
+    let mut found = false;
+
+    for entry_file in diary.entries()? {
+        if !opts.range.contains(&entry_file.date) {
+            continue;
+        }
+        let entry = diary.read_entry(&entry_file)?;
+        let title = if entry.title.is_empty() {
+            entry.date.format("%Y-%m-%d").to_string()
+        } else {
+            entry.title.clone()
+        };
+
+        for section in entry.sections_tagged(&opts.tag) {
+            found = true;
+            let heading = section.time.map_or_else(
+                || title.clone(),
+                |time| format!("{} — {}", title, time.format("%H:%M")),
+            );
+            content.push_str(&file_type.heading(&heading, 2));
+            if !section.body.is_empty() {
+                content.push_str(&section.body);
+                content.push_str("\n\n");
+            }
+        }
+    }
+
+    if !found {
+        return Err(DiaryError::UnknownTag {
+            tag: opts.tag.clone(),
+        });
+    }
+    Ok(content.trim_end().to_owned() + "\n")
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::prelude::*;
+
+    use super::{tag, tags, TagOptions};
+    use crate::{config::Config, ops::testing, utils::date::DateRange, Diary};
+
+    #[test]
+    fn count_tags() {
+        let first_date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+        let second_date = Local.with_ymd_and_hms(2021, 12, 1, 0, 0, 0).unwrap();
+        let config = testing::diary_with_entries(&[
+            (first_date, "Meeting", "Planned the release."),
+            (first_date, "Home", "Built a shelf."),
+            (second_date, "Meeting", "Reviewed the release."),
+        ]);
+        let diary = Diary::from_config(&config).unwrap();
+
+        let summaries = tags(&diary).unwrap();
+
+        assert_eq!(summaries.len(), 2);
+        assert_eq!(summaries[0].tag, "Home");
+        assert_eq!(summaries[1].tag, "Meeting");
+        assert_eq!(summaries[1].count, 2);
+        assert_eq!(
+            summaries[1].first,
+            NaiveDate::from_ymd_opt(2021, 11, 6).unwrap()
+        );
+        assert_eq!(
+            summaries[1].last,
+            NaiveDate::from_ymd_opt(2021, 12, 1).unwrap()
+        );
+    }
+
+    #[test]
+    fn count_tags_with_tag_format() {
+        let config = Config::builder()
+            .diary_path(testing::temp_diary_path())
+            .tag_format("{tag} ({day} {month})")
+            .build();
+        testing::default_init(config.diary_path());
+        testing::add_entries(
+            &config,
+            &[
+                (
+                    Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap(),
+                    "home",
+                    "Shelf.",
+                ),
+                (
+                    Local.with_ymd_and_hms(2021, 11, 7, 0, 0, 0).unwrap(),
+                    "home",
+                    "Sofa.",
+                ),
+            ],
+        );
+        let diary = Diary::from_config(&config).unwrap();
+
+        let summaries = tags(&diary).unwrap();
+
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(summaries[0].tag, "home");
+        assert_eq!(summaries[0].count, 2);
+    }
+
+    #[test]
+    fn collect_tag() {
+        let first_date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+        let second_date = Local.with_ymd_and_hms(2021, 12, 1, 0, 0, 0).unwrap();
+        let config = testing::diary_with_entries(&[
+            (first_date, "Meeting", "Planned the release."),
+            (first_date, "Home", "Built a shelf."),
+            (second_date, "Meeting", "Reviewed the release."),
+        ]);
+        let diary = Diary::from_config(&config).unwrap();
+
+        let opts = TagOptions {
+            tag: "Meeting".to_owned(),
+            range: DateRange::default(),
+        };
+        let content = tag(&opts, &diary).unwrap();
+
+        assert_eq!(
+            content,
+            "# Meeting\n\n\
+             ## Saturday 6<sup>th</sup> November 2021\n\nPlanned the release.\n\n\
+             ## Wednesday 1<sup>st</sup> December 2021\n\nReviewed the release.\n"
+        );
+    }
+
+    #[test]
+    fn collect_tag_in_range() {
+        let first_date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+        let second_date = Local.with_ymd_and_hms(2021, 12, 1, 0, 0, 0).unwrap();
+        let config = testing::diary_with_entries(&[
+            (first_date, "Meeting", "Planned the release."),
+            (first_date, "Home", "Built a shelf."),
+            (second_date, "Meeting", "Reviewed the release."),
+        ]);
+        let diary = Diary::from_config(&config).unwrap();
+
+        let opts = TagOptions {
+            tag: "Meeting".to_owned(),
+            range: DateRange::month(2021, 12).unwrap(),
+        };
+        let content = tag(&opts, &diary).unwrap();
+
+        assert!(!content.contains("Planned"));
+        assert!(content.contains("Reviewed the release."));
+    }
+
+    #[test]
+    #[should_panic(expected = "value: UnknownTag")]
+    fn collect_unknown_tag() {
+        let date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+        let config = testing::diary_with_entries(&[(date, "Meeting", "Planned the release.")]);
+        let diary = Diary::from_config(&config).unwrap();
+
+        let opts = TagOptions {
+            tag: "Holiday".to_owned(),
+            range: DateRange::default(),
+        };
+        tag(&opts, &diary).unwrap();
+    }
+}
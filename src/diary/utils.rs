@@ -1,4 +1,8 @@
 pub mod date;
 pub mod editing;
 pub mod file_system;
+pub mod format;
 pub mod git;
+pub mod html;
+pub mod layout;
+pub mod locale;
@@ -1,19 +1,56 @@
 use std::str::FromStr;
 
-use chrono::{DateTime, Local, NaiveDate, ParseError, TimeZone};
-use clap::ArgMatches;
+use chrono::{DateTime, Datelike, Local, NaiveDate, ParseError, TimeZone};
+use clap::{Arg, ArgMatches};
 
-pub const fn date_superscript(day: u32) -> &'static str {
-    let unit = day % 10;
+use crate::errors::DiaryError;
 
-    match unit {
-        1 => "st",
-        2 => "nd",
-        3 => "rd",
+pub const fn date_superscript(day: u32) -> &'static str {
+    match (day % 100, day % 10) {
+        (11..=13, _) => "th",
+        (_, 1) => "st",
+        (_, 2) => "nd",
+        (_, 3) => "rd",
         _ => "th",
     }
 }
 
+/// How the ordinal suffix of the day is written in entry titles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OrdinalStyle {
+    /// Raised using the file type's superscript markup, e.g. `6<sup>th</sup>`.
+    #[default]
+    Superscript,
+    /// Written in line with the day, e.g. `6th`.
+    Inline,
+    /// Left out, e.g. `6`.
+    None,
+}
+
+impl FromStr for OrdinalStyle {
+    type Err = DiaryError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "superscript" => Ok(Self::Superscript),
+            "inline" => Ok(Self::Inline),
+            "none" => Ok(Self::None),
+            _ => Err(DiaryError::BadOrdinalStyle {
+                style: s.to_owned(),
+            }),
+        }
+    }
+}
+
+/// The start of the given day in the local timezone.
+pub fn local_date(date: NaiveDate) -> DateTime<Local> {
+    let midnight = date.and_hms_opt(0, 0, 0).unwrap();
+    Local
+        .from_local_datetime(&midnight)
+        .earliest()
+        .unwrap_or_else(|| Local.from_utc_datetime(&midnight))
+}
+
 pub fn parse_date_option(args: &ArgMatches) -> Result<DateTime<Local>, ParseError> {
     Ok(match args.get_one::<String>("date") {
         Some(val) => {
@@ -24,16 +61,127 @@ pub fn parse_date_option(args: &ArgMatches) -> Result<DateTime<Local>, ParseErro
     })
 }
 
+/// A range of entry dates, with inclusive bounds. A missing bound leaves that end open.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DateRange {
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+}
+
+impl DateRange {
+    /// The given date alone.
+    pub const fn day(date: NaiveDate) -> Self {
+        Self {
+            from: Some(date),
+            to: Some(date),
+        }
+    }
+
+    /// Every date of the given month.
+    pub fn month(year: i32, month: u32) -> Option<Self> {
+        let from = NaiveDate::from_ymd_opt(year, month, 1)?;
+        let to = from
+            .checked_add_months(chrono::Months::new(1))?
+            .pred_opt()?;
+        Some(Self {
+            from: Some(from),
+            to: Some(to),
+        })
+    }
+
+    /// Every date of the given year.
+    pub fn year(year: i32) -> Option<Self> {
+        Some(Self {
+            from: Some(NaiveDate::from_ymd_opt(year, 1, 1)?),
+            to: Some(NaiveDate::from_ymd_opt(year, 12, 31)?),
+        })
+    }
+
+    /// The dates in both ranges.
+    #[must_use]
+    pub fn intersect(self, other: Self) -> Self {
+        Self {
+            from: self.from.max(other.from),
+            to: match (self.to, other.to) {
+                (Some(a), Some(b)) => Some(a.min(b)),
+                (a, b) => a.or(b),
+            },
+        }
+    }
+
+    pub fn contains(&self, date: &DateTime<Local>) -> bool {
+        let date = date.date_naive();
+        self.from.map_or(true, |from| from <= date) && self.to.map_or(true, |to| date <= to)
+    }
+}
+
+/// The `--from`, `--to`, `--month` and `--year` arguments, read by `parse_date_range`.
+pub fn date_range_args() -> [Arg; 4] {
+    [
+        Arg::new("from")
+            .long("from")
+            .value_name("DATE")
+            .help("Only include entries on or after this date. Use the %Y-%m-%d format."),
+        Arg::new("to")
+            .long("to")
+            .value_name("DATE")
+            .help("Only include entries on or before this date. Use the %Y-%m-%d format."),
+        Arg::new("month")
+            .long("month")
+            .value_name("MONTH")
+            .value_parser(clap::value_parser!(u32).range(1..=12))
+            .help("Only include entries from this month, 1 to 12, of --year or the current year."),
+        Arg::new("year")
+            .long("year")
+            .value_name("YEAR")
+            .value_parser(clap::value_parser!(i32))
+            .help("Only include entries from this year."),
+    ]
+}
+
+/// Reads the range given by the arguments of `date_range_args`.
+pub fn parse_date_range(args: &ArgMatches) -> Result<DateRange, DiaryError> {
+    let parse_date = |name: &str| {
+        args.get_one::<String>(name)
+            .map(|value| {
+                NaiveDate::from_str(value).map_err(|_| DiaryError::BadDateRange {
+                    reason: format!("`{}` is not a date in the %Y-%m-%d format", value),
+                })
+            })
+            .transpose()
+    };
+    let mut range = DateRange {
+        from: parse_date("from")?,
+        to: parse_date("to")?,
+    };
+
+    let year = args.get_one::<i32>("year").copied();
+    let period = match (year, args.get_one::<u32>("month").copied()) {
+        (year, Some(month)) => DateRange::month(year.unwrap_or_else(|| Local::now().year()), month),
+        (Some(year), None) => DateRange::year(year),
+        (None, None) => Some(DateRange::default()),
+    };
+    range = range.intersect(period.ok_or_else(|| DiaryError::BadDateRange {
+        reason: "the year is out of range".to_owned(),
+    })?);
+
+    Ok(range)
+}
+
 #[cfg(test)]
 mod tests {
-    use super::date_superscript;
+    use std::str::FromStr;
+
+    use chrono::{Local, NaiveDate, TimeZone};
+
+    use super::{date_superscript, DateRange, OrdinalStyle};
     #[test]
     fn date_superscript_st() {
         assert_eq!("st", date_superscript(21));
     }
     #[test]
     fn date_superscript_nd() {
-        assert_eq!("nd", date_superscript(12));
+        assert_eq!("nd", date_superscript(22));
     }
     #[test]
     fn date_superscript_rd() {
@@ -43,4 +191,40 @@ mod tests {
     fn date_superscript_th() {
         assert_eq!("th", date_superscript(17));
     }
+    #[test]
+    fn date_superscript_teens() {
+        assert_eq!("th", date_superscript(11));
+        assert_eq!("th", date_superscript(12));
+        assert_eq!("th", date_superscript(13));
+    }
+    #[test]
+    fn ordinal_style_from_str() {
+        assert_eq!(
+            OrdinalStyle::from_str("inline").unwrap(),
+            OrdinalStyle::Inline
+        );
+        assert!(OrdinalStyle::from_str("raised").is_err());
+    }
+
+    #[test]
+    fn month_range() {
+        let range = DateRange::month(2022, 2).unwrap();
+
+        assert_eq!(range.to, NaiveDate::from_ymd_opt(2022, 2, 28));
+        assert!(range.contains(&Local.with_ymd_and_hms(2022, 2, 28, 0, 0, 0).unwrap()));
+        assert!(!range.contains(&Local.with_ymd_and_hms(2022, 3, 1, 0, 0, 0).unwrap()));
+    }
+
+    #[test]
+    fn intersect_ranges() {
+        let open = DateRange {
+            from: NaiveDate::from_ymd_opt(2022, 1, 10),
+            to: None,
+        };
+
+        let range = open.intersect(DateRange::month(2022, 1).unwrap());
+
+        assert_eq!(range.from, NaiveDate::from_ymd_opt(2022, 1, 10));
+        assert_eq!(range.to, NaiveDate::from_ymd_opt(2022, 1, 31));
+    }
 }
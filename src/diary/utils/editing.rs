@@ -5,7 +5,7 @@ use std::{
 
 use crate::errors::DiaryError;
 
-pub type StringGetter = fn(S: String) -> io::Result<String>;
+pub type StringGetter = fn(s: String) -> io::Result<String>;
 
 pub fn add_user_content_to_file(file: &mut File, content: String) -> Result<(), DiaryError> {
     file.write_all(content.as_bytes())?;
@@ -1,5 +1,6 @@
 use std::{
-    fs::create_dir,
+    fs::{create_dir_all, read_dir},
+    io,
     path::{Path, PathBuf},
 };
 
@@ -7,21 +8,45 @@ use chrono::{DateTime, Local};
 
 use crate::errors::DiaryError;
 
+#[deprecated(note = "entry paths follow the diary's layout, use `Diary::get_entry_path`")]
 pub fn month_folder(path_root: &Path, date: &DateTime<Local>) -> PathBuf {
     let month_folder = PathBuf::from(date.format("%Y-%m").to_string());
     [path_root, &month_folder].iter().collect()
 }
 
+#[deprecated(note = "use `create_entry_folder`")]
 pub fn create_month_folder(path: &Path) -> Result<(), DiaryError> {
-    if path.exists() {
-        Ok(())
-    } else {
-        match create_dir(path) {
-            Ok(_) => Ok(()),
-            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
-                Err(DiaryError::UnInitialised { source: Some(e) })
-            }
-            Err(e) => panic!("Unexpected IO error {}", e), // uncovered.
+    create_entry_folder(path)
+}
+
+/// Creates the folder holding an entry, along with any missing parent folders.
+pub fn create_entry_folder(path: &Path) -> Result<(), DiaryError> {
+    match create_dir_all(path) {
+        Ok(_) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            Err(DiaryError::UnInitialised { source: Some(e) })
+        }
+        Err(e) => Err(e.into()), // uncovered.
+    }
+}
+
+/// Lists every file below the given folder, skipping hidden files and folders such as `.git`.
+pub fn walk_files(path_root: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut files = vec![];
+    for dir_entry in read_dir(path_root)? {
+        let path = dir_entry?.path();
+        let hidden = path
+            .file_name()
+            .map_or(true, |name| name.to_string_lossy().starts_with('.'));
+        if hidden {
+            continue;
+        }
+        if path.is_dir() {
+            files.extend(walk_files(&path)?);
+        } else {
+            files.push(path);
         }
     }
+    files.sort();
+    Ok(files)
 }
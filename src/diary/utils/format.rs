@@ -0,0 +1,231 @@
+//! # Format strings
+//!
+//! Simple `{placeholder}` templates used to customise what the diary writes.
+//! Literal braces are written as `{{` and `}}`.
+use regex::Regex;
+
+use crate::errors::DiaryError;
+
+/// The placeholders that describe the date of an entry.
+pub const DATE_PLACEHOLDERS: [&str; 7] = [
+    "weekday", "day", "ordinal", "month", "year", "iso_date", "week",
+];
+
+/// The placeholders available when formatting a tag, the date placeholders plus `{tag}`.
+pub const TAG_PLACEHOLDERS: [&str; 8] = [
+    "weekday", "day", "ordinal", "month", "year", "iso_date", "week", "tag",
+];
+
+/// The placeholders available in the layout of entry paths.
+pub const LAYOUT_PLACEHOLDERS: [&str; 9] = [
+    "year", "month", "day", "date", "isoyear", "isoweek", "weekday", "prefix", "ext",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Literal(String),
+    Placeholder(String),
+}
+
+/// A validated format string.
+#[derive(Debug, Clone)]
+pub struct FormatString {
+    segments: Vec<Segment>,
+}
+
+impl FormatString {
+    /// Parses a format string, only accepting the given placeholders.
+    ///
+    /// # Arguments
+    ///
+    /// * `format` - The raw format string, e.g. `{weekday} {day}{ordinal} {month}`.
+    /// * `placeholders` - The placeholder names that are valid in this context.
+    ///
+    /// # Returns
+    ///
+    /// The parsed format string or a DiaryError if it is malformed or uses an
+    /// unknown placeholder.
+    pub fn parse(format: &str, placeholders: &[&str]) -> Result<Self, DiaryError> {
+        let bad_format = |reason: String| DiaryError::BadFormat {
+            format: format.to_owned(),
+            reason,
+        };
+
+        let mut segments = vec![];
+        let mut literal = String::new();
+        let mut chars = format.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '{' => {
+                    let mut name = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => name.push(c),
+                            None => {
+                                return Err(bad_format(
+                                    "unclosed `{`, write `{{` for a literal brace".to_owned(),
+                                ))
+                            }
+                        }
+                    }
+                    if !placeholders.contains(&name.as_str()) {
+                        return Err(bad_format(format!("unknown placeholder `{{{}}}`", name)));
+                    }
+                    if !literal.is_empty() {
+                        segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                    }
+                    segments.push(Segment::Placeholder(name));
+                }
+                '}' => {
+                    return Err(bad_format(
+                        "unmatched `}`, write `}}` for a literal brace".to_owned(),
+                    ))
+                }
+                c => literal.push(c),
+            }
+        }
+        if !literal.is_empty() {
+            segments.push(Segment::Literal(literal));
+        }
+
+        Ok(Self { segments })
+    }
+
+    /// The names of the placeholders used, in the order they appear.
+    pub fn placeholders(&self) -> Vec<&str> {
+        self.segments
+            .iter()
+            .filter_map(|segment| match segment {
+                Segment::Placeholder(name) => Some(name.as_str()),
+                Segment::Literal(_) => None,
+            })
+            .collect()
+    }
+
+    /// A regular expression matching the rendered format string, with a capture
+    /// group for each placeholder in the order returned by `placeholders`.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - The regular expression matching the values of a placeholder.
+    pub fn regex(&self, pattern: impl Fn(&str) -> &'static str) -> String {
+        self.segments
+            .iter()
+            .map(|segment| match segment {
+                Segment::Literal(text) => regex::escape(text),
+                Segment::Placeholder(name) => format!("({})", pattern(name)),
+            })
+            .collect()
+    }
+
+    /// Reads the value of a placeholder back from a rendered format string, the inverse
+    /// of `render` when the values of the other placeholders are known.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The rendered format string.
+    /// * `placeholder` - The name of the placeholder to read.
+    /// * `value` - Gives the value of each other placeholder.
+    ///
+    /// # Returns
+    ///
+    /// The value of the placeholder, or None if the text doesn't follow the format.
+    pub fn read(
+        &self,
+        text: &str,
+        placeholder: &str,
+        value: impl Fn(&str) -> String,
+    ) -> Option<String> {
+        let mut captured = false;
+        let pattern: String = self
+            .segments
+            .iter()
+            .map(|segment| match segment {
+                Segment::Literal(text) => regex::escape(text),
+                Segment::Placeholder(name) if name == placeholder && !captured => {
+                    captured = true;
+                    "(.+)".to_owned()
+                }
+                Segment::Placeholder(name) if name == placeholder => ".+".to_owned(),
+                Segment::Placeholder(name) => regex::escape(&value(name)),
+            })
+            .collect();
+        let regex = Regex::new(&format!("^{}$", pattern)).ok()?;
+        regex
+            .captures(text)
+            .and_then(|captures| captures.get(1))
+            .map(|value| value.as_str().to_owned())
+    }
+
+    /// Renders the format string, asking `value` for the value of each placeholder.
+    pub fn render(&self, value: impl Fn(&str) -> String) -> String {
+        self.segments
+            .iter()
+            .map(|segment| match segment {
+                Segment::Literal(text) => text.to_owned(),
+                Segment::Placeholder(name) => value(name),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FormatString, DATE_PLACEHOLDERS, TAG_PLACEHOLDERS};
+
+    #[test]
+    fn render_placeholders() {
+        let format =
+            FormatString::parse("{weekday} the {day}{ordinal}", &DATE_PLACEHOLDERS).unwrap();
+
+        let rendered = format.render(|name| match name {
+            "weekday" => "Saturday".to_owned(),
+            "day" => "6".to_owned(),
+            _ => "th".to_owned(),
+        });
+
+        assert_eq!(rendered, "Saturday the 6th")
+    }
+
+    #[test]
+    fn read_placeholder() {
+        let format = FormatString::parse("{tag} ({day} {{x}})", &TAG_PLACEHOLDERS).unwrap();
+        let value = |_: &str| "6".to_owned();
+
+        assert_eq!(
+            format.read("home life (6 {x})", "tag", value).as_deref(),
+            Some("home life")
+        );
+        assert_eq!(format.read("home (7 {x})", "tag", value), None);
+        assert_eq!(format.read("home", "tag", value), None);
+    }
+
+    #[test]
+    fn escaped_braces() {
+        let format = FormatString::parse("{{{year}}}", &DATE_PLACEHOLDERS).unwrap();
+
+        assert_eq!(format.render(|_| "2021".to_owned()), "{2021}")
+    }
+
+    #[test]
+    #[should_panic(expected = "unknown placeholder `{tag}`")]
+    fn unknown_placeholder() {
+        FormatString::parse("{tag}", &DATE_PLACEHOLDERS).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "unclosed `{`")]
+    fn unclosed_placeholder() {
+        FormatString::parse("{day", &DATE_PLACEHOLDERS).unwrap();
+    }
+}
@@ -2,7 +2,7 @@ use std::path::Path;
 
 use git2::{Commit, Direction, ObjectType, Repository};
 
-pub fn find_last_commit(repo: &Repository) -> Result<Option<Commit>, git2::Error> {
+pub fn find_last_commit(repo: &Repository) -> Result<Option<Commit<'_>>, git2::Error> {
     let head = repo.head();
 
     let obj = match head {
@@ -44,6 +44,17 @@ pub fn add_and_commit(
     Ok(())
 }
 
+/// Stages the move of a file, as `git mv` would. Files that are not tracked are left alone.
+pub fn stage_move(repo: &Repository, from: &Path, to: &Path) -> Result<(), git2::Error> {
+    let mut index = repo.index()?;
+    if index.get_path(from, 0).is_none() {
+        return Ok(());
+    }
+    index.remove_path(from)?;
+    index.add_path(to)?;
+    index.write()
+}
+
 pub fn push_to_origin(repo: &Repository) -> Result<(), git2::Error> {
     let mut remote = repo.find_remote("origin")?;
     remote.connect(Direction::Push)?; // uncovered.
@@ -0,0 +1,259 @@
+//! # HTML
+//!
+//! Renders entry files as HTML. Markdown is rendered in full, the other file types
+//! have their headings, paragraphs, lists, superscripts and basic inline markup
+//! rendered, with any other text kept as it is.
+use std::sync::OnceLock;
+
+use pulldown_cmark::{html, Options, Parser};
+use regex::{Captures, Regex};
+
+use crate::{replace_superscripts, EntryContent, EntryFileType};
+
+/// Escapes text for use in HTML or XHTML.
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Closes the void elements of HTML, e.g. `<br>` becomes `<br />`, so HTML written
+/// by hand in an entry is also valid XHTML.
+pub fn close_void_elements(html: &str) -> String {
+    static VOID: OnceLock<Regex> = OnceLock::new();
+    let void = VOID.get_or_init(|| {
+        Regex::new(r"(?i)<(area|br|col|embed|hr|img|input|source|track|wbr)\b([^<>]*?)\s*/?>")
+            .expect("The void element pattern is valid.")
+    });
+    void.replace_all(html, "<$1$2 />").into_owned()
+}
+
+/// Renders the inline markup of a line of reStructuredText.
+fn rst_inline(text: &str) -> String {
+    static RULES: OnceLock<Vec<(Regex, &str)>> = OnceLock::new();
+    static LINK: OnceLock<Regex> = OnceLock::new();
+    let rules = RULES.get_or_init(|| {
+        [
+            (r"``([^`]+)``", "<code>$1</code>"),
+            (r"\*\*([^*]+)\*\*", "<strong>$1</strong>"),
+            (r"\*([^*]+)\*", "<em>$1</em>"),
+        ]
+        .into_iter()
+        .map(|(pattern, replacement)| {
+            let regex = Regex::new(pattern).expect("The rst inline patterns are valid.");
+            (regex, replacement)
+        })
+        .collect()
+    });
+    let mut html = text.to_owned();
+    for (regex, replacement) in rules {
+        html = regex.replace_all(&html, *replacement).into_owned();
+    }
+    let link = LINK.get_or_init(|| {
+        Regex::new(r"`([^`<]+?) &lt;([^`]+?)&gt;`__?").expect("The rst link pattern is valid.")
+    });
+    link.replace_all(&html, |captures: &Captures| {
+        format!(r#"<a href="{}">{}</a>"#, &captures[2], &captures[1])
+    })
+    .into_owned()
+}
+
+/// Renders a line of text of the given file type, escaping it and rendering its
+/// superscripts along with basic inline markup for reStructuredText.
+fn inline(file_type: &EntryFileType, text: &str) -> String {
+    let html = replace_superscripts(file_type.superscript("\0"), &escape(text), |inner| {
+        format!("<sup>{}</sup>", inner)
+    });
+    match file_type {
+        EntryFileType::RstDiary(_) => rst_inline(&html),
+        _ => html,
+    }
+}
+
+/// The blocks of text being built up while rendering.
+#[derive(Default)]
+struct Blocks {
+    html: String,
+    paragraph: Vec<String>,
+    list: Vec<String>,
+    literal: Vec<String>,
+}
+
+impl Blocks {
+    fn flush(&mut self) {
+        if !self.paragraph.is_empty() {
+            self.html
+                .push_str(&format!("<p>{}</p>\n", self.paragraph.join("\n")));
+            self.paragraph.clear();
+        }
+        if !self.list.is_empty() {
+            self.html.push_str("<ul>\n");
+            for item in self.list.drain(..) {
+                self.html.push_str(&format!("<li>{}</li>\n", item));
+            }
+            self.html.push_str("</ul>\n");
+        }
+        if !self.literal.is_empty() {
+            self.html.push_str(&format!(
+                "<pre><code>{}\n</code></pre>\n",
+                escape(&self.literal.join("\n"))
+            ));
+            self.literal.clear();
+        }
+    }
+}
+
+/// Renders the headings, paragraphs, lists and indented literal blocks of a file
+/// type other than markdown.
+fn render_blocks(file_type: &EntryFileType, lines: &[&str]) -> String {
+    let is_rst = matches!(file_type, EntryFileType::RstDiary(_));
+    let mut blocks = Blocks::default();
+    let mut i = 0;
+    while i < lines.len() {
+        let line = lines[i];
+        if let Some((text, level, span)) = file_type.read_heading(&lines[i..]) {
+            blocks.flush();
+            let level = level.min(6);
+            blocks.html.push_str(&format!(
+                "<h{level}>{}</h{level}>\n",
+                inline(file_type, &text),
+                level = level
+            ));
+            i += span;
+            continue;
+        }
+
+        let starts_literal = blocks.paragraph.is_empty() && blocks.list.is_empty();
+        if line.trim().is_empty() {
+            if blocks.literal.is_empty() {
+                blocks.flush();
+            } else {
+                blocks.literal.push(String::new());
+            }
+        } else if line.starts_with(char::is_whitespace)
+            && (starts_literal || !blocks.literal.is_empty())
+        {
+            blocks.literal.push(line.trim_start().to_owned());
+        } else if let Some(item) = line.strip_prefix("- ").or_else(|| line.strip_prefix("* ")) {
+            if !blocks.paragraph.is_empty() || !blocks.literal.is_empty() {
+                blocks.flush();
+            }
+            blocks.list.push(inline(file_type, item));
+        } else if !blocks.list.is_empty() && line.starts_with(char::is_whitespace) {
+            if let Some(item) = blocks.list.last_mut() {
+                item.push('\n');
+                item.push_str(&inline(file_type, line.trim()));
+            }
+        } else if is_rst && line.trim() == "::" {
+            blocks.flush();
+        } else {
+            if !blocks.list.is_empty() || !blocks.literal.is_empty() {
+                blocks.flush();
+            }
+            // A paragraph ending in `::` introduces a literal block in rst, shown with one colon.
+            let line = match line.strip_suffix("::") {
+                Some(text) if is_rst => format!("{}:", text),
+                _ => line.to_owned(),
+            };
+            blocks.paragraph.push(inline(file_type, &line));
+        }
+        i += 1;
+    }
+    blocks.flush();
+    blocks.html
+}
+
+/// Renders the content of an entry file as HTML, leaving out its front matter.
+///
+/// # Arguments
+///
+/// * `file_type` - The file type of the entry.
+/// * `content` - The content of the entry file.
+///
+/// # Returns
+///
+/// The HTML of the entry's title and body, without a surrounding document.
+pub fn render_entry(file_type: &EntryFileType, content: &str) -> String {
+    let lines: Vec<&str> = content.lines().collect();
+    let start = file_type
+        .read_front_matter(&lines)
+        .map_or(0, |(_, span)| span);
+
+    match file_type {
+        EntryFileType::MarkdownDiary(_) => {
+            let body = lines[start..].join("\n");
+            let mut html = String::new();
+            let options = Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH;
+            html::push_html(&mut html, Parser::new_ext(&body, options));
+            html
+        }
+        _ => render_blocks(file_type, &lines[start..]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::{close_void_elements, escape, render_entry};
+    use crate::EntryFileType;
+
+    #[test]
+    fn escape_html() {
+        assert_eq!(
+            escape("<a href=\"x\">&</a>"),
+            "&lt;a href=&quot;x&quot;&gt;&amp;&lt;/a&gt;"
+        );
+    }
+
+    #[test]
+    fn close_void() {
+        assert_eq!(
+            close_void_elements("a<br>b<BR/><img src=\"x.png\" alt=\"x\" /><hr class=\"y\">"),
+            "a<br />b<BR /><img src=\"x.png\" alt=\"x\" /><hr class=\"y\" />"
+        );
+    }
+
+    #[test]
+    fn render_md() {
+        let file_type = EntryFileType::from_str("md").unwrap();
+        let content = "---\ndate: 2021-11-06\ntags: []\n---\n\n\
+                       # Saturday 6<sup>th</sup> November 2021\n\n## work\n\nSome *notes*.\n";
+
+        assert_eq!(
+            render_entry(&file_type, content),
+            "<h1>Saturday 6<sup>th</sup> November 2021</h1>\n<h2>work</h2>\n<p>Some <em>notes</em>.</p>\n"
+        );
+    }
+
+    #[test]
+    fn render_rst() {
+        let file_type = EntryFileType::from_str("rst").unwrap();
+        let content = "Saturday 6\\ :sup:`th` November 2021\n\
+                       ===================================\n\n\
+                       work\n^^^^\n\n\
+                       Some **bold** and ``code`` <here>.\n\
+                       See `the docs <https://example.com>`_.\n\n\
+                       - one\n- two\n\n\
+                       ::\n\n    let x = 1;\n";
+
+        assert_eq!(
+            render_entry(&file_type, content),
+            "<h1>Saturday 6<sup>th</sup> November 2021</h1>\n\
+             <h2>work</h2>\n\
+             <p>Some <strong>bold</strong> and <code>code</code> &lt;here&gt;.\n\
+             See <a href=\"https://example.com\">the docs</a>.</p>\n\
+             <ul>\n<li>one</li>\n<li>two</li>\n</ul>\n\
+             <pre><code>let x = 1;\n</code></pre>\n"
+        );
+    }
+}
@@ -0,0 +1,219 @@
+//! # Layouts
+//!
+//! Where entry files are kept below the diary folder, described by a format string
+//! such as `{year}/{month}/{prefix}_{date}.{ext}`.
+use std::{
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use chrono::prelude::*;
+use regex::Regex;
+
+use crate::{
+    errors::DiaryError,
+    utils::format::{FormatString, LAYOUT_PLACEHOLDERS},
+};
+
+/// The layout used when none is configured, e.g. `2021-11/diary_2021-11-06.md`.
+pub const DEFAULT_LAYOUT: &str = "{year}-{month}/{prefix}_{date}.{ext}";
+
+/// The sets of placeholders that each identify a single day.
+const DAY_PLACEHOLDERS: [&[&str]; 3] = [
+    &["date"],
+    &["year", "month", "day"],
+    &["isoyear", "isoweek", "weekday"],
+];
+
+/// The regular expression matching the values of a layout placeholder.
+fn pattern(placeholder: &str) -> &'static str {
+    match placeholder {
+        "year" | "isoyear" => r"\d{4}",
+        "month" | "day" | "isoweek" => r"\d{2}",
+        "date" => r"\d{4}-\d{2}-\d{2}",
+        "weekday" => "[A-Za-z]+",
+        "ext" => "[A-Za-z0-9]+",
+        _ => "[^/]*?",
+    }
+}
+
+/// A validated layout of entry paths.
+#[derive(Debug, Clone)]
+pub struct Layout {
+    format: FormatString,
+    regex: Regex,
+}
+
+impl Layout {
+    /// Parses a layout, checking that it identifies a single day and ends with the extension.
+    ///
+    /// # Returns
+    ///
+    /// The parsed layout or a DiaryError if it is malformed, uses an unknown placeholder,
+    /// points outside the diary folder or could give two days the same path.
+    pub fn parse(layout: &str) -> Result<Self, DiaryError> {
+        let bad_format = |reason: &str| DiaryError::BadFormat {
+            format: layout.to_owned(),
+            reason: reason.to_owned(),
+        };
+
+        let format = FormatString::parse(layout, &LAYOUT_PLACEHOLDERS)?;
+        if layout.starts_with('/') || Path::new(layout).is_absolute() {
+            return Err(bad_format(
+                "the layout must be relative to the diary folder",
+            ));
+        }
+        if layout.split(['/', '\\']).any(|segment| segment == "..") {
+            return Err(bad_format(
+                "the layout must not leave the diary folder with `..`",
+            ));
+        }
+        if !layout.ends_with(".{ext}") {
+            return Err(bad_format("the layout must end with `.{ext}`"));
+        }
+        let placeholders = format.placeholders();
+        let identifies_day = DAY_PLACEHOLDERS
+            .iter()
+            .any(|names| names.iter().all(|name| placeholders.contains(name)));
+        if !identifies_day {
+            return Err(bad_format(
+                "the layout must identify a single day using `{date}`, `{year}`, `{month}` and `{day}`, or `{isoyear}`, `{isoweek}` and `{weekday}`",
+            ));
+        }
+
+        let regex = Regex::new(&format!("^{}$", format.regex(pattern)))
+            .map_err(|e| bad_format(&e.to_string()))?;
+        Ok(Self { format, regex })
+    }
+
+    /// The path of the entry for the given date, relative to the diary folder.
+    pub fn render(&self, date: &DateTime<Local>, prefix: &str, extension: &str) -> PathBuf {
+        let path = self.format.render(|name| match name {
+            "year" => date.format("%Y").to_string(),
+            "month" => date.format("%m").to_string(),
+            "day" => date.format("%d").to_string(),
+            "date" => date.format("%Y-%m-%d").to_string(),
+            "isoyear" => date.format("%G").to_string(),
+            "isoweek" => date.format("%V").to_string(),
+            "weekday" => date.format("%A").to_string(),
+            "prefix" => prefix.to_owned(),
+            "ext" => extension.to_owned(),
+            _ => String::new(),
+        });
+        path.split('/').collect()
+    }
+
+    /// The inverse of `render`. Reads the date of the entry at the given path,
+    /// relative to the diary folder.
+    ///
+    /// # Returns
+    ///
+    /// The date, or None if the path is not that of an entry with the given prefix
+    /// and extension.
+    pub fn read(&self, path: &Path, prefix: &str, extension: &str) -> Option<NaiveDate> {
+        let components: Option<Vec<&str>> = path
+            .components()
+            .map(|component| component.as_os_str().to_str())
+            .collect();
+        let text = components?.join("/");
+        let captures = self.regex.captures(&text)?;
+
+        let placeholders = self.format.placeholders();
+        let value = |name: &str| {
+            let index = placeholders.iter().position(|p| *p == name)?;
+            captures.get(index + 1).map(|m| m.as_str())
+        };
+        let number = |name: &str| value(name)?.parse::<u32>().ok();
+
+        let date = NaiveDate::parse_from_str(value("date").unwrap_or_default(), "%Y-%m-%d")
+            .ok()
+            .or_else(|| {
+                NaiveDate::from_ymd_opt(number("year")? as i32, number("month")?, number("day")?)
+            })
+            .or_else(|| {
+                let weekday = Weekday::from_str(value("weekday")?).ok()?;
+                NaiveDate::from_isoywd_opt(number("isoyear")? as i32, number("isoweek")?, weekday)
+            })?;
+
+        let local_date = crate::utils::date::local_date(date);
+        (self.render(&local_date, prefix, extension) == path).then_some(date)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use chrono::prelude::*;
+
+    use super::{Layout, DEFAULT_LAYOUT};
+
+    #[test]
+    fn render_default_layout() {
+        let layout = Layout::parse(DEFAULT_LAYOUT).unwrap();
+        let date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+
+        assert_eq!(
+            layout.render(&date, "diary", "md"),
+            PathBuf::from("2021-11").join("diary_2021-11-06.md")
+        );
+    }
+
+    #[test]
+    fn read_inverts_render() {
+        let entry_date = Local.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap();
+
+        for pattern in [
+            DEFAULT_LAYOUT,
+            "{year}/{month}/{prefix}_{date}.{ext}",
+            "{year}/{month}/{day}.{ext}",
+            "{isoyear}/week-{isoweek}/{weekday}.{ext}",
+        ] {
+            let layout = Layout::parse(pattern).unwrap();
+            let path = layout.render(&entry_date, "diary", "md");
+
+            assert_eq!(
+                layout.read(&path, "diary", "md"),
+                Some(entry_date.date_naive()),
+                "{}",
+                pattern
+            );
+            assert_eq!(layout.read(&path, "diary", "rst"), None);
+        }
+    }
+
+    #[test]
+    fn read_ignores_other_files() {
+        let layout = Layout::parse("{year}/{month}/{prefix}_{date}.{ext}").unwrap();
+
+        let path = PathBuf::from("2021").join("12").join("diary_2021-11-06.md");
+        assert_eq!(layout.read(&path, "diary", "md"), None);
+
+        let path = PathBuf::from("2021").join("11").join("notes.md");
+        assert_eq!(layout.read(&path, "diary", "md"), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "identify a single day")]
+    fn layout_without_day() {
+        Layout::parse("{year}/week-{isoweek}/{weekday}.{ext}").unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "must not leave the diary folder")]
+    fn layout_with_parent_folder() {
+        Layout::parse("../{year}/{date}.{ext}").unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "must be relative to the diary folder")]
+    fn absolute_layout() {
+        Layout::parse("/tmp/{date}.{ext}").unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "must end with `.{ext}`")]
+    fn layout_without_extension() {
+        Layout::parse("{year}/{date}").unwrap();
+    }
+}
@@ -0,0 +1,292 @@
+//! # Locales
+//!
+//! Weekday names, month names and ordinal suffixes used when writing entry titles.
+use std::str::FromStr;
+
+use chrono::{DateTime, Datelike, Local};
+
+use crate::{errors::DiaryError, utils::date};
+
+/// Decides how a day of the month is written as an ordinal in a locale.
+pub trait OrdinalStrategy {
+    /// The suffix marking the day as an ordinal, e.g. `th` for the 6th.
+    fn suffix(&self, day: u32) -> &'static str;
+
+    /// Whether the suffix is conventionally written as superscript.
+    fn raised(&self) -> bool {
+        true
+    }
+}
+
+/// English ordinals, e.g. 1st, 2nd, 6th.
+pub struct EnglishOrdinals;
+
+impl OrdinalStrategy for EnglishOrdinals {
+    fn suffix(&self, day: u32) -> &'static str {
+        date::date_superscript(day)
+    }
+}
+
+/// French ordinals, only the first of the month is an ordinal, e.g. 1er.
+pub struct FrenchOrdinals;
+
+impl OrdinalStrategy for FrenchOrdinals {
+    fn suffix(&self, day: u32) -> &'static str {
+        match day {
+            1 => "er",
+            _ => "",
+        }
+    }
+}
+
+/// German ordinals, marked with a trailing full stop, e.g. 6.
+pub struct GermanOrdinals;
+
+impl OrdinalStrategy for GermanOrdinals {
+    fn suffix(&self, _day: u32) -> &'static str {
+        "."
+    }
+
+    fn raised(&self) -> bool {
+        false
+    }
+}
+
+/// For locales that write the day of the month as a plain number.
+pub struct NoOrdinals;
+
+impl OrdinalStrategy for NoOrdinals {
+    fn suffix(&self, _day: u32) -> &'static str {
+        ""
+    }
+}
+
+/// The locales entry titles can be written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Locale {
+    #[default]
+    English,
+    French,
+    German,
+    Spanish,
+    Dutch,
+}
+
+impl Locale {
+    /// The language code of the locale, e.g. `fr`.
+    pub const fn code(self) -> &'static str {
+        match self {
+            Self::English => "en",
+            Self::French => "fr",
+            Self::German => "de",
+            Self::Spanish => "es",
+            Self::Dutch => "nl",
+        }
+    }
+
+    const fn weekdays(self) -> [&'static str; 7] {
+        match self {
+            Self::English => [
+                "Monday",
+                "Tuesday",
+                "Wednesday",
+                "Thursday",
+                "Friday",
+                "Saturday",
+                "Sunday",
+            ],
+            Self::French => [
+                "lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi", "dimanche",
+            ],
+            Self::German => [
+                "Montag",
+                "Dienstag",
+                "Mittwoch",
+                "Donnerstag",
+                "Freitag",
+                "Samstag",
+                "Sonntag",
+            ],
+            Self::Spanish => [
+                "lunes",
+                "martes",
+                "miércoles",
+                "jueves",
+                "viernes",
+                "sábado",
+                "domingo",
+            ],
+            Self::Dutch => [
+                "maandag",
+                "dinsdag",
+                "woensdag",
+                "donderdag",
+                "vrijdag",
+                "zaterdag",
+                "zondag",
+            ],
+        }
+    }
+
+    const fn months(self) -> [&'static str; 12] {
+        match self {
+            Self::English => [
+                "January",
+                "February",
+                "March",
+                "April",
+                "May",
+                "June",
+                "July",
+                "August",
+                "September",
+                "October",
+                "November",
+                "December",
+            ],
+            Self::French => [
+                "janvier",
+                "février",
+                "mars",
+                "avril",
+                "mai",
+                "juin",
+                "juillet",
+                "août",
+                "septembre",
+                "octobre",
+                "novembre",
+                "décembre",
+            ],
+            Self::German => [
+                "Januar",
+                "Februar",
+                "März",
+                "April",
+                "Mai",
+                "Juni",
+                "Juli",
+                "August",
+                "September",
+                "Oktober",
+                "November",
+                "Dezember",
+            ],
+            Self::Spanish => [
+                "enero",
+                "febrero",
+                "marzo",
+                "abril",
+                "mayo",
+                "junio",
+                "julio",
+                "agosto",
+                "septiembre",
+                "octubre",
+                "noviembre",
+                "diciembre",
+            ],
+            Self::Dutch => [
+                "januari",
+                "februari",
+                "maart",
+                "april",
+                "mei",
+                "juni",
+                "juli",
+                "augustus",
+                "september",
+                "oktober",
+                "november",
+                "december",
+            ],
+        }
+    }
+
+    /// The name of the weekday of the given date.
+    pub fn weekday(self, date: &DateTime<Local>) -> &'static str {
+        self.weekdays()[date.weekday().num_days_from_monday() as usize]
+    }
+
+    /// The name of the month of the given date.
+    pub fn month(self, date: &DateTime<Local>) -> &'static str {
+        self.months()[date.month0() as usize]
+    }
+
+    /// The ordinal strategy of the locale.
+    pub const fn ordinals(self) -> &'static dyn OrdinalStrategy {
+        match self {
+            Self::English => &EnglishOrdinals,
+            Self::French => &FrenchOrdinals,
+            Self::German => &GermanOrdinals,
+            Self::Spanish | Self::Dutch => &NoOrdinals,
+        }
+    }
+
+    /// The title format used when no `title_format` is configured.
+    pub const fn title_format(self) -> &'static str {
+        match self {
+            Self::English | Self::French | Self::Dutch => "{weekday} {day}{ordinal} {month} {year}",
+            Self::German => "{weekday}, {day}{ordinal} {month} {year}",
+            Self::Spanish => "{weekday} {day}{ordinal} de {month} de {year}",
+        }
+    }
+}
+
+impl FromStr for Locale {
+    type Err = DiaryError;
+
+    /// Parses a language code such as `fr`, ignoring any region, e.g. `fr_FR`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let language = s.split(['_', '-']).next().unwrap_or_default();
+        match language.to_lowercase().as_str() {
+            "en" => Ok(Self::English),
+            "fr" => Ok(Self::French),
+            "de" => Ok(Self::German),
+            "es" => Ok(Self::Spanish),
+            "nl" => Ok(Self::Dutch),
+            _ => Err(DiaryError::BadLocale {
+                locale: s.to_owned(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use chrono::{Local, TimeZone};
+
+    use super::Locale;
+
+    #[test]
+    fn locale_names() {
+        let date = Local.with_ymd_and_hms(2021, 11, 6, 0, 0, 0).unwrap();
+
+        assert_eq!(Locale::French.weekday(&date), "samedi");
+        assert_eq!(Locale::German.month(&date), "November");
+        assert_eq!(Locale::Spanish.month(&date), "noviembre");
+    }
+
+    #[test]
+    fn locale_ordinals() {
+        assert_eq!(Locale::English.ordinals().suffix(2), "nd");
+        assert_eq!(Locale::French.ordinals().suffix(1), "er");
+        assert_eq!(Locale::French.ordinals().suffix(2), "");
+        assert_eq!(Locale::German.ordinals().suffix(6), ".");
+        assert!(!Locale::German.ordinals().raised());
+    }
+
+    #[test]
+    fn locale_with_region() {
+        assert_eq!(Locale::from_str("nl_BE").unwrap(), Locale::Dutch);
+        assert_eq!(Locale::from_str("DE").unwrap(), Locale::German);
+    }
+
+    #[test]
+    #[should_panic(expected = "value: BadLocale")]
+    fn unsupported_locale() {
+        Locale::from_str("xx").unwrap();
+    }
+}
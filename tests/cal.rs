@@ -0,0 +1,43 @@
+use std::process::Command;
+
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+
+mod utils;
+
+#[test]
+fn test_cal_month() -> utils::TestReturn {
+    let mut cmd = Command::cargo_bin("diary")?;
+
+    let (dir_str, config_path) = utils::create_temp_dir_and_path()?;
+
+    cmd.args(["--config", config_path.to_str().unwrap(), "init", &dir_str]);
+    cmd.assert().success();
+
+    let mut cmd = Command::cargo_bin("diary")?;
+    cmd.args([
+        "--config",
+        config_path.to_str().unwrap(),
+        "cal",
+        "--year",
+        "2022",
+        "--month",
+        "1",
+    ]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::starts_with("    January 2022\nMo Tu We"))
+        .stdout(predicate::str::contains("31"));
+
+    let mut cmd = Command::cargo_bin("diary")?;
+    cmd.args([
+        "--config",
+        config_path.to_str().unwrap(),
+        "cal",
+        "--month",
+        "13",
+    ]);
+    cmd.assert().failure();
+
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use std::{fs, path::PathBuf, process::Command};
+
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+
+mod utils;
+
+#[test]
+fn test_convert_success() -> utils::TestReturn {
+    let mut cmd = Command::cargo_bin("diary")?;
+
+    let (dir_str, config_path) = utils::create_temp_dir_and_path()?;
+
+    cmd.args(["--config", config_path.to_str().unwrap(), "init", &dir_str]);
+    cmd.assert().success();
+
+    let mut cmd = Command::cargo_bin("diary")?;
+    cmd.args(["--config", config_path.to_str().unwrap(), "new"]);
+    cmd.assert().success();
+
+    let mut cmd = Command::cargo_bin("diary")?;
+    cmd.args([
+        "--config",
+        config_path.to_str().unwrap(),
+        "convert",
+        "--to",
+        "rst",
+    ]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Converted 1 entries."));
+
+    let content = fs::read_to_string(&config_path)?;
+    assert!(content.contains("file_type = 'rst'"));
+
+    let diary_path: PathBuf = [&dir_str, "diary"].iter().collect();
+    let month_folder = diary_path.read_dir()?.next().unwrap()?.path();
+    let entry_path = month_folder.read_dir()?.next().unwrap()?.path();
+
+    assert_eq!(entry_path.extension().unwrap(), "rst");
+
+    let mut cmd = Command::cargo_bin("diary")?;
+    cmd.args(["--config", config_path.to_str().unwrap(), "open"]);
+    cmd.env("EDITOR", "true");
+    cmd.assert().success();
+
+    Ok(())
+}
+
+#[test]
+fn test_convert_dry_run() -> utils::TestReturn {
+    let mut cmd = Command::cargo_bin("diary")?;
+
+    let (dir_str, config_path) = utils::create_temp_dir_and_path()?;
+
+    cmd.args(["--config", config_path.to_str().unwrap(), "init", &dir_str]);
+    cmd.assert().success();
+
+    let mut cmd = Command::cargo_bin("diary")?;
+    cmd.args(["--config", config_path.to_str().unwrap(), "new"]);
+    cmd.assert().success();
+
+    let mut cmd = Command::cargo_bin("diary")?;
+    cmd.args([
+        "--config",
+        config_path.to_str().unwrap(),
+        "convert",
+        "--to",
+        "org",
+        "--dry-run",
+    ]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("1 entries would be converted."));
+
+    let content = fs::read_to_string(&config_path)?;
+    assert!(content.contains("file_type = 'md'"));
+
+    Ok(())
+}
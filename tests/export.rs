@@ -0,0 +1,205 @@
+use std::{fs, io::Read, path::Path, process::Command};
+
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+
+mod utils;
+
+#[test]
+fn test_export_html() -> utils::TestReturn {
+    let mut cmd = Command::cargo_bin("diary")?;
+
+    let (dir_str, config_path) = utils::create_temp_dir_and_path()?;
+
+    cmd.args(["--config", config_path.to_str().unwrap(), "init", &dir_str]);
+    cmd.assert().success();
+
+    let mut cmd = Command::cargo_bin("diary")?;
+    cmd.args(["--config", config_path.to_str().unwrap(), "new"]);
+    cmd.assert().success();
+
+    let mut cmd = Command::cargo_bin("diary")?;
+    cmd.args([
+        "--config",
+        config_path.to_str().unwrap(),
+        "add",
+        "--tag",
+        "Meeting",
+        "Planned the *release*.",
+    ]);
+    cmd.assert().success();
+
+    let out_dir = Path::new(&dir_str).join("site");
+    let mut cmd = Command::cargo_bin("diary")?;
+    cmd.args([
+        "--config",
+        config_path.to_str().unwrap(),
+        "export",
+        "html",
+        out_dir.to_str().unwrap(),
+    ]);
+    cmd.assert().success().stdout(predicate::str::starts_with(
+        "Exported 1 entries and 0 attachments",
+    ));
+
+    let index = fs::read_to_string(out_dir.join("index.html"))?;
+    assert!(index.contains("years/"));
+    let tags = fs::read_to_string(out_dir.join("tags.html"))?;
+    assert!(tags.contains("<h2 id=\"tag-meeting\">Meeting</h2>"));
+    assert!(out_dir.join("style.css").exists());
+
+    let mut cmd = Command::cargo_bin("diary")?;
+    cmd.args(["--config", config_path.to_str().unwrap(), "export"]);
+    cmd.assert().failure();
+
+    Ok(())
+}
+
+#[test]
+fn test_export_json_and_csv() -> utils::TestReturn {
+    let mut cmd = Command::cargo_bin("diary")?;
+
+    let (dir_str, config_path) = utils::create_temp_dir_and_path()?;
+
+    cmd.args(["--config", config_path.to_str().unwrap(), "init", &dir_str]);
+    cmd.assert().success();
+
+    let mut cmd = Command::cargo_bin("diary")?;
+    cmd.args(["--config", config_path.to_str().unwrap(), "new"]);
+    cmd.assert().success();
+
+    let mut cmd = Command::cargo_bin("diary")?;
+    cmd.args([
+        "--config",
+        config_path.to_str().unwrap(),
+        "add",
+        "--tag",
+        "Meeting",
+        "Planned the release.",
+    ]);
+    cmd.assert().success();
+
+    let mut cmd = Command::cargo_bin("diary")?;
+    cmd.args(["--config", config_path.to_str().unwrap(), "export", "json"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::starts_with("[\n"))
+        .stdout(predicate::str::contains("\"tag\": \"Meeting\""))
+        .stdout(predicate::str::contains("<sup>").not());
+
+    let csv_path = Path::new(&dir_str).join("entries.csv");
+    let mut cmd = Command::cargo_bin("diary")?;
+    cmd.args([
+        "--config",
+        config_path.to_str().unwrap(),
+        "export",
+        "csv",
+        "--output",
+        csv_path.to_str().unwrap(),
+    ]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::starts_with("Exported to"));
+
+    let csv = fs::read_to_string(csv_path)?;
+    assert!(csv.starts_with("date,name,path,title,tags,words,preamble,sections\n"));
+    assert!(csv.contains(",Meeting,3,"));
+
+    let mut cmd = Command::cargo_bin("diary")?;
+    cmd.args([
+        "--config",
+        config_path.to_str().unwrap(),
+        "export",
+        "json",
+        "--year",
+        "1999",
+    ]);
+    cmd.assert().success().stdout("[]\n");
+
+    Ok(())
+}
+
+#[test]
+fn test_export_book() -> utils::TestReturn {
+    let mut cmd = Command::cargo_bin("diary")?;
+
+    let (dir_str, config_path) = utils::create_temp_dir_and_path()?;
+
+    cmd.args(["--config", config_path.to_str().unwrap(), "init", &dir_str]);
+    cmd.assert().success();
+
+    let mut cmd = Command::cargo_bin("diary")?;
+    cmd.args(["--config", config_path.to_str().unwrap(), "new"]);
+    cmd.assert().success();
+
+    let mut cmd = Command::cargo_bin("diary")?;
+    cmd.args([
+        "--config",
+        config_path.to_str().unwrap(),
+        "add",
+        "--tag",
+        "Meeting",
+        "Planned the release.",
+    ]);
+    cmd.assert().success();
+
+    let mut cmd = Command::cargo_bin("diary")?;
+    cmd.args(["--config", config_path.to_str().unwrap(), "export", "book"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::starts_with("# Diary\n\n## Contents\n\n- ["))
+        .stdout(predicate::str::contains("\n### Meeting\n"))
+        .stdout(predicate::str::contains("Planned the release."));
+
+    let mut cmd = Command::cargo_bin("diary")?;
+    cmd.args([
+        "--config",
+        config_path.to_str().unwrap(),
+        "export",
+        "book",
+        "--from",
+        "1999-01-01",
+        "--to",
+        "1999-03-31",
+    ]);
+    cmd.assert().failure();
+
+    Ok(())
+}
+
+#[test]
+fn test_export_epub() -> utils::TestReturn {
+    let mut cmd = Command::cargo_bin("diary")?;
+
+    let (dir_str, config_path) = utils::create_temp_dir_and_path()?;
+
+    cmd.args(["--config", config_path.to_str().unwrap(), "init", &dir_str]);
+    cmd.assert().success();
+
+    let mut cmd = Command::cargo_bin("diary")?;
+    cmd.args(["--config", config_path.to_str().unwrap(), "new"]);
+    cmd.assert().success();
+
+    let epub_path = Path::new(&dir_str).join("diary.epub");
+    let mut cmd = Command::cargo_bin("diary")?;
+    cmd.args([
+        "--config",
+        config_path.to_str().unwrap(),
+        "export",
+        "epub",
+        epub_path.to_str().unwrap(),
+    ]);
+    cmd.assert().success().stdout(predicate::str::starts_with(
+        "Exported 1 entries in 1 chapters",
+    ));
+
+    let mut archive = zip::ZipArchive::new(fs::File::open(&epub_path)?)?;
+    let mut mimetype = String::new();
+    archive.by_index(0)?.read_to_string(&mut mimetype)?;
+    assert_eq!(mimetype, "application/epub+zip");
+    assert!(archive.by_name("META-INF/container.xml").is_ok());
+    assert!(archive.by_name("OEBPS/content.opf").is_ok());
+    assert!(archive.by_name("OEBPS/nav.xhtml").is_ok());
+
+    Ok(())
+}
@@ -0,0 +1,104 @@
+use std::{fs, path::Path, process::Command};
+
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+
+mod utils;
+
+#[test]
+fn test_import_jrnl() -> utils::TestReturn {
+    let mut cmd = Command::cargo_bin("diary")?;
+
+    let (dir_str, config_path) = utils::create_temp_dir_and_path()?;
+
+    cmd.args(["--config", config_path.to_str().unwrap(), "init", &dir_str]);
+    cmd.assert().success();
+
+    let jrnl_path = Path::new(&dir_str).join("journal.txt");
+    fs::write(
+        &jrnl_path,
+        "[2021-11-06 09:30] Planned the release. @work\n\n[2021-11-07 10:00] Rested.\n",
+    )?;
+
+    let mut cmd = Command::cargo_bin("diary")?;
+    cmd.args([
+        "--config",
+        config_path.to_str().unwrap(),
+        "import",
+        "jrnl",
+        jrnl_path.to_str().unwrap(),
+    ]);
+    cmd.assert()
+        .success()
+        .stdout("Imported 2 entries, creating 2 new diary entries.\n");
+
+    let entry = fs::read_to_string(Path::new(&dir_str).join("diary/2021-11/diary_2021-11-06.md"))?;
+    assert!(entry.contains("### 09:30 — work\n\nPlanned the release. @work\n"));
+
+    let mut cmd = Command::cargo_bin("diary")?;
+    cmd.args([
+        "--config",
+        config_path.to_str().unwrap(),
+        "import",
+        "jrnl",
+        jrnl_path.to_str().unwrap(),
+    ]);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("pass --merge to add to it"));
+
+    let mut cmd = Command::cargo_bin("diary")?;
+    cmd.args([
+        "--config",
+        config_path.to_str().unwrap(),
+        "import",
+        "jrnl",
+        "--merge",
+        jrnl_path.to_str().unwrap(),
+    ]);
+    cmd.assert().success().stdout(
+        "Imported 0 entries, creating 0 new diary entries.\n\
+         Skipped 2 entries already in the diary.\n",
+    );
+
+    let merged = fs::read_to_string(Path::new(&dir_str).join("diary/2021-11/diary_2021-11-06.md"))?;
+    assert_eq!(merged, entry);
+
+    Ok(())
+}
+
+#[test]
+fn test_import_dayone() -> utils::TestReturn {
+    let mut cmd = Command::cargo_bin("diary")?;
+
+    let (dir_str, config_path) = utils::create_temp_dir_and_path()?;
+
+    cmd.args(["--config", config_path.to_str().unwrap(), "init", &dir_str]);
+    cmd.assert().success();
+
+    let export_path = Path::new(&dir_str).join("Journal.json");
+    fs::write(
+        &export_path,
+        r#"{"entries": [{"creationDate": "2021-11-06T12:00:00Z", "text": "Lunch.", "tags": ["food"]}]}"#,
+    )?;
+
+    let mut cmd = Command::cargo_bin("diary")?;
+    cmd.args([
+        "--config",
+        config_path.to_str().unwrap(),
+        "import",
+        "dayone",
+        export_path.to_str().unwrap(),
+    ]);
+    cmd.assert()
+        .success()
+        .stdout("Imported 1 entries, creating 1 new diary entries.\n");
+
+    let mut cmd = Command::cargo_bin("diary")?;
+    cmd.args(["--config", config_path.to_str().unwrap(), "tag", "food"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Lunch."));
+
+    Ok(())
+}
@@ -94,3 +94,147 @@ fn test_init_failure() -> utils::TestReturn {
     cmd.assert().failure();
     Ok(())
 }
+
+#[test]
+fn test_init_success_with_org_file_type() -> utils::TestReturn {
+    let mut cmd = Command::cargo_bin("diary")?;
+
+    let (dir_str, config_path) = utils::create_temp_dir_and_path()?;
+
+    cmd.args([
+        "--config",
+        config_path.to_str().unwrap(),
+        "init",
+        &dir_str,
+        "--filetype",
+        "org",
+    ]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Initialised diary."));
+
+    let content = fs::read_to_string(&config_path).expect("Unable to read file.");
+
+    assert!(content.contains("file_type = 'org'"));
+
+    let mut cmd = Command::cargo_bin("diary")?;
+    cmd.args(["--config", config_path.to_str().unwrap(), "new"]);
+    cmd.assert().success();
+
+    let mut cmd = Command::cargo_bin("diary")?;
+    cmd.args([
+        "--config",
+        config_path.to_str().unwrap(),
+        "add",
+        "--tag",
+        "Meeting",
+        "Org content",
+    ]);
+    cmd.assert().success();
+
+    let diary_path: PathBuf = [&dir_str, "diary"].iter().collect();
+    let month_folder = diary_path.read_dir()?.next().unwrap()?.path();
+    let entry_path = month_folder.read_dir()?.next().unwrap()?.path();
+
+    assert_eq!(entry_path.extension().unwrap(), "org");
+
+    let content = fs::read_to_string(entry_path)?;
+
+    assert!(content.starts_with("* "));
+    assert!(content.contains("** Meeting\n\nOrg content\n"));
+
+    Ok(())
+}
+
+#[test]
+fn test_init_success_with_adoc_and_txt_file_types() -> utils::TestReturn {
+    for file_type in ["adoc", "txt"] {
+        let mut cmd = Command::cargo_bin("diary")?;
+
+        let (dir_str, config_path) = utils::create_temp_dir_and_path()?;
+
+        cmd.args([
+            "--config",
+            config_path.to_str().unwrap(),
+            "init",
+            &dir_str,
+            "--filetype",
+            file_type,
+        ]);
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::contains("Initialised diary."));
+
+        let content = fs::read_to_string(config_path).expect("Unable to read file.");
+
+        assert!(content.contains(&format!("file_type = '{}'", file_type)));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_init_named_diary() -> utils::TestReturn {
+    let (dir_str, config_path) = utils::create_temp_dir_and_path()?;
+    let (work_dir_str, _) = utils::create_temp_dir_and_path()?;
+
+    let mut cmd = Command::cargo_bin("diary")?;
+    cmd.args(["--config", config_path.to_str().unwrap(), "init", &dir_str]);
+    cmd.assert().success();
+
+    let mut cmd = Command::cargo_bin("diary")?;
+    cmd.args([
+        "--config",
+        config_path.to_str().unwrap(),
+        "--diary",
+        "work",
+        "init",
+        &work_dir_str,
+        "--prefix",
+        "log",
+    ]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Initialised diary."));
+
+    let content = fs::read_to_string(&config_path)?;
+    assert!(content.contains("prefix = 'diary'"));
+    assert!(content.contains("[diaries.work]"));
+    assert!(content.contains("prefix = 'log'"));
+
+    let mut cmd = Command::cargo_bin("diary")?;
+    cmd.args([
+        "--config",
+        config_path.to_str().unwrap(),
+        "new",
+        "--diary",
+        "work",
+    ]);
+    cmd.assert().success();
+
+    let work_path: PathBuf = [&work_dir_str, "diary"].iter().collect();
+    let month_folder = work_path.read_dir()?.next().unwrap()?.path();
+    let entry_path = month_folder.read_dir()?.next().unwrap()?.path();
+    assert!(entry_path
+        .file_name()
+        .unwrap()
+        .to_string_lossy()
+        .starts_with("log_"));
+
+    let diary_path: PathBuf = [&dir_str, "diary"].iter().collect();
+    assert_eq!(diary_path.read_dir()?.count(), 0);
+
+    let mut cmd = Command::cargo_bin("diary")?;
+    cmd.args([
+        "--config",
+        config_path.to_str().unwrap(),
+        "--diary",
+        "home",
+        "new",
+    ]);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("There is no diary called `home`"));
+
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use std::process::Command;
+
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+
+mod utils;
+
+#[test]
+fn test_list_entries() -> utils::TestReturn {
+    let mut cmd = Command::cargo_bin("diary")?;
+
+    let (dir_str, config_path) = utils::create_temp_dir_and_path()?;
+
+    cmd.args(["--config", config_path.to_str().unwrap(), "init", &dir_str]);
+    cmd.assert().success();
+
+    let mut cmd = Command::cargo_bin("diary")?;
+    cmd.args(["--config", config_path.to_str().unwrap(), "new"]);
+    cmd.assert().success();
+
+    let mut cmd = Command::cargo_bin("diary")?;
+    cmd.args([
+        "--config",
+        config_path.to_str().unwrap(),
+        "add",
+        "--tag",
+        "work",
+        "Meetings.",
+    ]);
+    cmd.assert().success();
+
+    let mut cmd = Command::cargo_bin("diary")?;
+    cmd.args(["--config", config_path.to_str().unwrap(), "list"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("bytes  work"));
+
+    let mut cmd = Command::cargo_bin("diary")?;
+    cmd.args(["--config", config_path.to_str().unwrap(), "list", "--json"]);
+    cmd.assert().success().stdout(predicate::str::contains(
+        "\"tags\": [\n      \"work\"\n    ]",
+    ));
+
+    let mut cmd = Command::cargo_bin("diary")?;
+    cmd.args([
+        "--config",
+        config_path.to_str().unwrap(),
+        "list",
+        "--year",
+        "1999",
+    ]);
+    cmd.assert().success().stdout(predicate::str::is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_list_bad_date() -> utils::TestReturn {
+    let mut cmd = Command::cargo_bin("diary")?;
+
+    let (dir_str, config_path) = utils::create_temp_dir_and_path()?;
+
+    cmd.args(["--config", config_path.to_str().unwrap(), "init", &dir_str]);
+    cmd.assert().success();
+
+    let mut cmd = Command::cargo_bin("diary")?;
+    cmd.args([
+        "--config",
+        config_path.to_str().unwrap(),
+        "list",
+        "--from",
+        "yesterday",
+    ]);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid date range"));
+
+    Ok(())
+}
@@ -0,0 +1,86 @@
+use std::{fs, path::PathBuf, process::Command};
+
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+
+mod utils;
+
+#[test]
+fn test_migrate_prefix() -> utils::TestReturn {
+    let mut cmd = Command::cargo_bin("diary")?;
+
+    let (dir_str, config_path) = utils::create_temp_dir_and_path()?;
+
+    cmd.args(["--config", config_path.to_str().unwrap(), "init", &dir_str]);
+    cmd.assert().success();
+
+    let mut cmd = Command::cargo_bin("diary")?;
+    cmd.args(["--config", config_path.to_str().unwrap(), "new"]);
+    cmd.assert().success();
+
+    let content = fs::read_to_string(&config_path)?;
+    fs::write(
+        &config_path,
+        content.replace("prefix = 'diary'", "prefix = 'journal'"),
+    )?;
+
+    let mut cmd = Command::cargo_bin("diary")?;
+    cmd.args([
+        "--config",
+        config_path.to_str().unwrap(),
+        "migrate",
+        "--from-prefix",
+        "diary",
+        "--dry-run",
+    ]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("1 entries would be moved."));
+
+    let mut cmd = Command::cargo_bin("diary")?;
+    cmd.args([
+        "--config",
+        config_path.to_str().unwrap(),
+        "migrate",
+        "--from-prefix",
+        "diary",
+    ]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Moved 1 entries."));
+
+    let diary_path: PathBuf = [&dir_str, "diary"].iter().collect();
+    let month_folder = diary_path.read_dir()?.next().unwrap()?.path();
+    let entry_path = month_folder.read_dir()?.next().unwrap()?.path();
+
+    assert!(entry_path
+        .file_name()
+        .unwrap()
+        .to_string_lossy()
+        .starts_with("journal_"));
+
+    let mut cmd = Command::cargo_bin("diary")?;
+    cmd.args(["--config", config_path.to_str().unwrap(), "open"]);
+    cmd.env("EDITOR", "true");
+    cmd.assert().success();
+
+    Ok(())
+}
+
+#[test]
+fn test_migrate_needs_old_prefix_or_layout() -> utils::TestReturn {
+    let mut cmd = Command::cargo_bin("diary")?;
+
+    let (dir_str, config_path) = utils::create_temp_dir_and_path()?;
+
+    cmd.args(["--config", config_path.to_str().unwrap(), "init", &dir_str]);
+    cmd.assert().success();
+
+    let mut cmd = Command::cargo_bin("diary")?;
+    cmd.args(["--config", config_path.to_str().unwrap(), "migrate"]);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("--from-prefix"));
+
+    Ok(())
+}
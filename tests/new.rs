@@ -41,3 +41,69 @@ fn test_new_without_init() -> utils::TestReturn {
 
     Ok(())
 }
+
+#[test]
+fn test_new_missing_template() -> utils::TestReturn {
+    let mut cmd = Command::cargo_bin("diary")?;
+
+    let (dir_str, config_path) = utils::create_temp_dir_and_path()?;
+
+    cmd.args(["--config", config_path.to_str().unwrap(), "init", &dir_str]);
+    cmd.assert().success();
+
+    let mut cmd = Command::cargo_bin("diary")?;
+
+    cmd.args([
+        "--config",
+        config_path.to_str().unwrap(),
+        "new",
+        "--template",
+        "retro",
+    ]);
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "The template `retro` was not found",
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_new_named_entry() -> utils::TestReturn {
+    let mut cmd = Command::cargo_bin("diary")?;
+
+    let (dir_str, config_path) = utils::create_temp_dir_and_path()?;
+
+    cmd.args(["--config", config_path.to_str().unwrap(), "init", &dir_str]);
+    cmd.assert().success();
+
+    for args in [vec!["new"], vec!["new", "--name", "evening"]] {
+        let mut cmd = Command::cargo_bin("diary")?;
+        cmd.args(["--config", config_path.to_str().unwrap()]);
+        cmd.args(args);
+        cmd.assert().success();
+    }
+
+    let mut cmd = Command::cargo_bin("diary")?;
+    cmd.args([
+        "--config",
+        config_path.to_str().unwrap(),
+        "add",
+        "--name",
+        "evening",
+        "Evening thoughts.",
+    ]);
+    cmd.assert().success();
+
+    let diary_path: PathBuf = [&dir_str, "diary"].iter().collect();
+    let month_folder = diary_path.read_dir()?.next().unwrap()?.path();
+    let mut entry_names: Vec<String> = month_folder
+        .read_dir()?
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .collect();
+    entry_names.sort();
+
+    assert_eq!(entry_names.len(), 2);
+    assert!(entry_names[1].ends_with("_evening.md"));
+
+    Ok(())
+}
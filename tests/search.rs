@@ -0,0 +1,62 @@
+use std::process::Command;
+
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+
+mod utils;
+
+#[test]
+fn test_search_tag() -> utils::TestReturn {
+    let mut cmd = Command::cargo_bin("diary")?;
+
+    let (dir_str, config_path) = utils::create_temp_dir_and_path()?;
+
+    cmd.args(["--config", config_path.to_str().unwrap(), "init", &dir_str]);
+    cmd.assert().success();
+
+    let mut cmd = Command::cargo_bin("diary")?;
+    cmd.args(["--config", config_path.to_str().unwrap(), "new"]);
+    cmd.assert().success();
+
+    for (tag, content) in [("work", "Fixed the build."), ("home", "Built a shelf.")] {
+        let mut cmd = Command::cargo_bin("diary")?;
+        cmd.args([
+            "--config",
+            config_path.to_str().unwrap(),
+            "add",
+            "--tag",
+            tag,
+            content,
+        ]);
+        cmd.assert().success();
+    }
+
+    let mut cmd = Command::cargo_bin("diary")?;
+    cmd.args([
+        "--config",
+        config_path.to_str().unwrap(),
+        "search",
+        "-i",
+        "--tag",
+        "home",
+        "BUILT",
+    ]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("[home]"))
+        .stdout(predicate::str::contains("> Built a shelf."))
+        .stdout(predicate::str::contains("build").not());
+
+    let mut cmd = Command::cargo_bin("diary")?;
+    cmd.args([
+        "--config",
+        config_path.to_str().unwrap(),
+        "search",
+        "holiday",
+    ]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("No matches found."));
+
+    Ok(())
+}
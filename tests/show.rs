@@ -0,0 +1,96 @@
+use std::{fs, path::Path, process::Command};
+
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+
+mod utils;
+
+#[test]
+fn test_show_entry() -> utils::TestReturn {
+    let mut cmd = Command::cargo_bin("diary")?;
+
+    let (dir_str, config_path) = utils::create_temp_dir_and_path()?;
+
+    cmd.args(["--config", config_path.to_str().unwrap(), "init", &dir_str]);
+    cmd.assert().success();
+
+    let mut cmd = Command::cargo_bin("diary")?;
+    cmd.args(["--config", config_path.to_str().unwrap(), "new"]);
+    cmd.assert().success();
+
+    let mut cmd = Command::cargo_bin("diary")?;
+    cmd.args([
+        "--config",
+        config_path.to_str().unwrap(),
+        "add",
+        "--tag",
+        "work",
+        "Meetings.",
+    ]);
+    cmd.assert().success();
+
+    let mut cmd = Command::cargo_bin("diary")?;
+    cmd.args(["--config", config_path.to_str().unwrap(), "show"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("work\n----\n\nMeetings."))
+        .stdout(predicate::str::contains("<sup>").not());
+
+    let mut cmd = Command::cargo_bin("diary")?;
+    cmd.args([
+        "--config",
+        config_path.to_str().unwrap(),
+        "show",
+        "--last",
+        "1",
+        "--raw",
+    ]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("## work\n\nMeetings."));
+
+    let mut cmd = Command::cargo_bin("diary")?;
+    cmd.args([
+        "--config",
+        config_path.to_str().unwrap(),
+        "show",
+        "--date",
+        "1999-01-01",
+    ]);
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "The desired entry has not been found.",
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_show_date_west_of_utc() -> utils::TestReturn {
+    let mut cmd = Command::cargo_bin("diary")?;
+
+    let (dir_str, config_path) = utils::create_temp_dir_and_path()?;
+
+    cmd.args(["--config", config_path.to_str().unwrap(), "init", &dir_str]);
+    cmd.assert().success();
+
+    let month_path = Path::new(&dir_str).join("diary/2021-11");
+    fs::create_dir_all(&month_path)?;
+    fs::write(
+        month_path.join("diary_2021-11-06.md"),
+        "# Saturday 6<sup>th</sup> November 2021\n\n",
+    )?;
+
+    let mut cmd = Command::cargo_bin("diary")?;
+    cmd.env("TZ", "America/New_York").args([
+        "--config",
+        config_path.to_str().unwrap(),
+        "show",
+        "--date",
+        "2021-11-06",
+    ]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Saturday 6th November 2021"));
+
+    Ok(())
+}
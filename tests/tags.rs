@@ -0,0 +1,55 @@
+use std::process::Command;
+
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+
+mod utils;
+
+#[test]
+fn test_tags_and_tag() -> utils::TestReturn {
+    let mut cmd = Command::cargo_bin("diary")?;
+
+    let (dir_str, config_path) = utils::create_temp_dir_and_path()?;
+
+    cmd.args(["--config", config_path.to_str().unwrap(), "init", &dir_str]);
+    cmd.assert().success();
+
+    let mut cmd = Command::cargo_bin("diary")?;
+    cmd.args(["--config", config_path.to_str().unwrap(), "new"]);
+    cmd.assert().success();
+
+    for content in ["Planned the release.", "Reviewed the release."] {
+        let mut cmd = Command::cargo_bin("diary")?;
+        cmd.args([
+            "--config",
+            config_path.to_str().unwrap(),
+            "add",
+            "--tag",
+            "Meeting",
+            content,
+        ]);
+        cmd.assert().success();
+    }
+
+    let mut cmd = Command::cargo_bin("diary")?;
+    cmd.args(["--config", config_path.to_str().unwrap(), "tags"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::starts_with("Meeting     2  "));
+
+    let mut cmd = Command::cargo_bin("diary")?;
+    cmd.args(["--config", config_path.to_str().unwrap(), "tag", "Meeting"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::starts_with("# Meeting\n"))
+        .stdout(predicate::str::contains("Planned the release."))
+        .stdout(predicate::str::contains("Reviewed the release."));
+
+    let mut cmd = Command::cargo_bin("diary")?;
+    cmd.args(["--config", config_path.to_str().unwrap(), "tag", "Holiday"]);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("No entries have sections tagged"));
+
+    Ok(())
+}